@@ -0,0 +1,16 @@
+// Column metadata generated by `PostgreSQL` as `COLUMNS`, in field declaration order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnMeta {
+    // Rust field name, e.g. `created_at`
+    pub field: &'static str,
+    // Unquoted SQL column name, e.g. `createdAt`
+    pub column: &'static str,
+    // Renamed alias used by `alias::ALL`, e.g. `template_created_at`
+    pub alias: &'static str,
+    // Declared `#[psql(types = ...)]` type, e.g. `DateTime<Utc>`
+    pub types: &'static str,
+    pub nullable: bool,
+    pub key: bool,
+    pub searchable: bool,
+    pub encrypted: bool,
+}
\ No newline at end of file
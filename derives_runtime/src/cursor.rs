@@ -0,0 +1,107 @@
+use serde_json::Value;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+// Opaque keyset cursor holding the sort values of a boundary row
+// Format: URL safe base64 (no padding) of the JSON array of sort values
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Cursor(Vec<Value>);
+
+impl Cursor {
+    pub fn new(values: Vec<Value>) -> Self {
+        Self(values)
+    }
+
+    pub fn values(&self) -> &[Value] {
+        &self.0
+    }
+
+    pub fn encode(&self) -> String {
+        let bytes = Value::Array(self.0.clone()).to_string().into_bytes();
+        let mut token = String::new();
+
+        for chunk in bytes.chunks(3) {
+            let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+            let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+            for i in 0..chunk.len() + 1 {
+                token.push(ALPHABET[(n >> (18 - i * 6) & 63) as usize] as char);
+            }
+        }
+
+        token
+    }
+
+    pub fn decode(token: &str) -> Option<Self> {
+        let mut bytes = vec![];
+
+        for chunk in token.as_bytes().chunks(4) {
+            if chunk.len() < 2 {
+                return None;
+            }
+
+            let mut n = 0;
+            for (i, c) in chunk.iter().enumerate() {
+                let v = ALPHABET.iter().position(|a| a == c)? as u32;
+                n |= v << (18 - i * 6);
+            }
+
+            for i in 0..chunk.len() - 1 {
+                bytes.push((n >> (16 - i * 8) & 255) as u8);
+            }
+        }
+
+        match serde_json::from_slice::<Value>(&bytes).ok()? {
+            Value::Array(values) => Some(Self(values)),
+            _ => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::Cursor;
+
+    #[test]
+    fn encodes_url_safe_base64_without_padding() {
+        assert_eq!(Cursor::new(vec![json!("a")]).encode(), "WyJhIl0");
+        assert_eq!(Cursor::new(vec![json!("~?>")]).encode(), "WyJ-Pz4iXQ");
+        assert_eq!(Cursor::default().encode(), "W10");
+    }
+
+    #[test]
+    fn round_trips_every_remainder() {
+        for n in 0..6 {
+            let cursor = Cursor::new(vec![json!("x".repeat(n)), json!(n)]);
+            assert_eq!(Cursor::decode(&cursor.encode()), Some(cursor));
+        }
+    }
+
+    #[test]
+    fn round_trips_mixed_values() {
+        let cursor = Cursor::new(vec![json!("héllo, wörld"), json!(-12.5), Value::Null, json!(true), json!({"a": [1]})]);
+        let token = cursor.encode();
+
+        assert!(token.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_eq!(Cursor::decode(&token).unwrap().values(), cursor.values());
+    }
+
+    #[test]
+    fn rejects_malformed_tokens() {
+        assert_eq!(Cursor::decode(""), None);
+        assert_eq!(Cursor::decode("W"), None);
+        assert_eq!(Cursor::decode("WyJhIl0A"), None);
+        assert_eq!(Cursor::decode("WyJhIl0="), None);
+        assert_eq!(Cursor::decode("WyJh+l0"), None);
+        assert_eq!(Cursor::decode("!!!!"), None);
+    }
+
+    #[test]
+    fn rejects_non_array_json() {
+        // `{"a":1}` and `1`
+        assert_eq!(Cursor::decode("eyJhIjoxfQ"), None);
+        assert_eq!(Cursor::decode("MQ"), None);
+    }
+}
\ No newline at end of file
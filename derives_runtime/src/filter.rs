@@ -0,0 +1,203 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+// Single filter used by `PostgreSQL` generated Page types
+// Format: `{ "cols": "createdAt", "ops": "Gte", "vals": "2024-01-01T00:00:00Z" }`
+#[derive(Default, Debug, Clone, PartialEq)]
+#[derive(Deserialize, Serialize)]
+pub struct Filter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cols: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ops: Option<FilterOps>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vals: Option<Value>,
+}
+
+// Filter operators, serialized as their variant name (`"Eq"`) with snake case aliases (`"eq"`)
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Deserialize, Serialize)]
+pub enum FilterOps {
+    #[default]
+    #[serde(alias = "eq")]
+    Eq,
+    #[serde(alias = "neq")]
+    Neq,
+    #[serde(alias = "gt")]
+    Gt,
+    #[serde(alias = "gte")]
+    Gte,
+    #[serde(alias = "lt")]
+    Lt,
+    #[serde(alias = "lte")]
+    Lte,
+    #[serde(alias = "like")]
+    Like,
+    #[serde(alias = "like_left")]
+    LikeLeft,
+    #[serde(alias = "like_right")]
+    LikeRight,
+    #[serde(alias = "ilike")]
+    ILike,
+    #[serde(alias = "in")]
+    In,
+    #[serde(alias = "not_in")]
+    NotIn,
+    #[serde(alias = "between")]
+    Between,
+    #[serde(alias = "is_null")]
+    IsNull,
+    #[serde(alias = "is_not_null")]
+    IsNotNull,
+    #[serde(alias = "starts_with")]
+    StartsWith,
+}
+
+// Filter tree used by `PostgreSQL` generated Page types
+// Format: `[...]` or `{ "and": [...] }` combine with AND, `{ "or": [...] }` with OR, `{ "not": ... }` negates
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterNode {
+    And(Vec<FilterNode>),
+    Or(Vec<FilterNode>),
+    Not(Box<FilterNode>),
+    Filter(Filter),
+}
+
+impl Default for FilterNode {
+    fn default() -> Self {
+        Self::And(vec![])
+    }
+}
+
+impl FilterNode {
+    // Number of nested groups, a single filter has a depth of 0
+    pub fn depth(&self) -> usize {
+        match self {
+            Self::And(items) | Self::Or(items) => 1 + items.iter().map(Self::depth).max().unwrap_or(0),
+            Self::Not(item) => 1 + item.depth(),
+            Self::Filter(_) => 0,
+        }
+    }
+
+    pub fn from_value(value: Value) -> Result<Self, String> {
+        let group = |items: Value, key: &str| match items {
+            Value::Array(items) => items.into_iter().map(Self::from_value).collect::<Result<Vec<_>, _>>(),
+            _ => Err(format!("filter group `{}` expects an array", key)),
+        };
+
+        match value {
+            Value::Array(items) => Ok(Self::And(group(Value::Array(items), "and")?)),
+            Value::Object(mut map) => {
+                let key = ["and", "or", "not"].into_iter().find(|key| map.contains_key(*key));
+
+                match key {
+                    Some(key) if map.len() > 1 => Err(format!("filter group `{}` can't be mixed with other keys", key)),
+                    Some("and") => Ok(Self::And(group(map.remove("and").unwrap_or_default(), "and")?)),
+                    Some("or") => Ok(Self::Or(group(map.remove("or").unwrap_or_default(), "or")?)),
+                    Some(_) => Ok(Self::Not(Box::new(Self::from_value(map.remove("not").unwrap_or_default())?))),
+                    None => serde_json::from_value::<Filter>(Value::Object(map))
+                        .map(Self::Filter)
+                        .map_err(|e| e.to_string()),
+                }
+            },
+            _ => Err(String::from("expected a filter, an array of filters or a filter group")),
+        }
+    }
+
+    pub fn to_value(&self) -> Value {
+        match self {
+            Self::And(items) => serde_json::json!({ "and": items.iter().map(Self::to_value).collect::<Vec<_>>() }),
+            Self::Or(items) => serde_json::json!({ "or": items.iter().map(Self::to_value).collect::<Vec<_>>() }),
+            Self::Not(item) => serde_json::json!({ "not": item.to_value() }),
+            Self::Filter(filter) => serde_json::to_value(filter).unwrap_or_default(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for FilterNode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::from_value(Value::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for FilterNode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_value().serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde_json::json;
+
+    use super::{Filter, FilterNode, FilterOps};
+
+    // Shared with the `PageQuery` tests
+    pub(crate) fn filter(cols: &str, ops: FilterOps, vals: Option<serde_json::Value>) -> FilterNode {
+        FilterNode::Filter(Filter { cols: Some(cols.to_string()), ops: Some(ops), vals })
+    }
+
+    #[test]
+    fn reads_a_flat_array_as_and() {
+        let node = FilterNode::from_value(json!([
+            { "cols": "name", "ops": "like", "vals": "foo" },
+            { "cols": "age", "ops": "Gte", "vals": 18 }
+        ])).unwrap();
+
+        assert_eq!(node, FilterNode::And(vec![
+            filter("name", FilterOps::Like, Some(json!("foo"))),
+            filter("age", FilterOps::Gte, Some(json!(18))),
+        ]));
+        assert_eq!(node.depth(), 1);
+    }
+
+    #[test]
+    fn reads_nested_groups() {
+        let node = FilterNode::from_value(json!({
+            "or": [
+                { "cols": "status", "vals": "PAID" },
+                { "not": { "and": [{ "cols": "total", "ops": "Lt", "vals": 0 }] } }
+            ]
+        })).unwrap();
+
+        assert_eq!(node, FilterNode::Or(vec![
+            FilterNode::Filter(Filter { cols: Some(String::from("status")), ops: None, vals: Some(json!("PAID")) }),
+            FilterNode::Not(Box::new(FilterNode::And(vec![filter("total", FilterOps::Lt, Some(json!(0)))]))),
+        ]));
+        assert_eq!(node.depth(), 3);
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let node = FilterNode::Or(vec![
+            filter("name", FilterOps::In, Some(json!(["a", "b"]))),
+            FilterNode::Not(Box::new(filter("name", FilterOps::IsNull, None))),
+        ]);
+
+        assert_eq!(FilterNode::from_value(node.to_value()), Ok(node.clone()));
+        assert_eq!(serde_json::from_str::<FilterNode>(&serde_json::to_string(&node).unwrap()).unwrap(), node);
+    }
+
+    #[test]
+    fn rejects_malformed_groups() {
+        assert_eq!(
+            FilterNode::from_value(json!({ "or": { "cols": "name" } })),
+            Err(String::from("filter group `or` expects an array"))
+        );
+        assert_eq!(
+            FilterNode::from_value(json!({ "and": [], "cols": "name" })),
+            Err(String::from("filter group `and` can't be mixed with other keys"))
+        );
+        assert_eq!(
+            FilterNode::from_value(json!("name")),
+            Err(String::from("expected a filter, an array of filters or a filter group"))
+        );
+        assert!(FilterNode::from_value(json!([{ "cols": "name", "ops": "Bogus" }])).is_err());
+    }
+
+    #[test]
+    fn defaults_to_an_empty_and() {
+        assert_eq!(FilterNode::default(), FilterNode::And(vec![]));
+        assert_eq!(FilterNode::default().depth(), 1);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,15 @@
+mod column;
+mod cursor;
+mod filter;
+mod order;
+mod parse;
+mod query;
+mod variant;
+
+pub use column::ColumnMeta;
+pub use cursor::Cursor;
+pub use filter::{Filter, FilterNode, FilterOps};
+pub use order::{Order, OrderNulls, OrderOps};
+pub use parse::{ParseError, ParseErrorKind};
+pub use query::PageQuery;
+pub use variant::UnknownVariant;
\ No newline at end of file
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+// Single sort key used by `PostgreSQL` generated Page types
+// Format: `{ "cols": "createdAt", "ops": "Desc", "nulls": "Last" }`
+#[derive(Default, Debug, Clone, PartialEq)]
+#[derive(Deserialize, Serialize)]
+pub struct Order {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cols: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ops: Option<OrderOps>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nulls: Option<OrderNulls>,
+}
+
+// Sort directions, serialized as `"Asc"` or `"Desc"` with lowercase aliases
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Deserialize, Serialize)]
+pub enum OrderOps {
+    #[default]
+    #[serde(alias = "asc")]
+    Asc,
+    #[serde(alias = "desc")]
+    Desc,
+}
+
+// Position of NULLs, defaults to last for `Asc` and first for `Desc` like PostgreSQL
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Deserialize, Serialize)]
+pub enum OrderNulls {
+    #[serde(alias = "first")]
+    First,
+    #[serde(alias = "last")]
+    Last,
+}
\ No newline at end of file
@@ -0,0 +1,45 @@
+// Column level failure raised by `PostgreSQL` generated `try_parse`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub column: String,
+    pub kind: ParseErrorKind,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
+    Missing,
+    Decode,
+    UnexpectedNull,
+}
+
+impl ParseError {
+    pub fn missing<T: ToString>(column: T) -> Self {
+        let column = column.to_string();
+        let message = format!("column `{}` is missing from the row", column);
+
+        Self { column, kind: ParseErrorKind::Missing, message }
+    }
+
+    pub fn decode<T: ToString, U: ToString>(column: T, error: U) -> Self {
+        let column = column.to_string();
+        let message = format!("column `{}` could not be decoded: {}", column, error.to_string());
+
+        Self { column, kind: ParseErrorKind::Decode, message }
+    }
+
+    pub fn unexpected_null<T: ToString>(column: T) -> Self {
+        let column = column.to_string();
+        let message = format!("column `{}` is required but was NULL", column);
+
+        Self { column, kind: ParseErrorKind::UnexpectedNull, message }
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
\ No newline at end of file
@@ -0,0 +1,194 @@
+use serde_json::Value;
+
+use crate::filter::{Filter, FilterNode, FilterOps};
+use crate::order::{Order, OrderNulls, OrderOps};
+
+// Page request read from a query string by `PostgreSQL` generated Page types
+// Format: `filter[name][like]=foo&sort=-createdAt,name&page=2&perPage=25&search=bar&after=...`
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct PageQuery {
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+    pub search: Option<String>,
+    pub filters: Option<FilterNode>,
+    pub orders: Option<Vec<Order>>,
+    pub after: Option<String>,
+    pub before: Option<String>,
+}
+
+impl PageQuery {
+    // Pairs are already percent decoded, keys outside of the page request are ignored
+    pub fn from_pairs(pairs: Vec<(String, String)>) -> Result<Self, String> {
+        let mut query = Self::default();
+        let mut filters = vec![];
+
+        for (key, value) in pairs {
+            match key.as_str() {
+                "page" => query.page = Some(Self::number(&key, &value)?),
+                "perPage" | "per_page" => query.per_page = Some(Self::number(&key, &value)?),
+                "search" => query.search = Some(value),
+                "sort" => query.orders = Some(Self::sort(&value)?),
+                "after" => query.after = Some(value),
+                "before" => query.before = Some(value),
+                _ if key == "filter" || key.starts_with("filter[") => {
+                    filters.push(FilterNode::Filter(Self::filter(&key, &value)?));
+                },
+                _ => {},
+            }
+        }
+
+        if !filters.is_empty() {
+            query.filters = Some(FilterNode::And(filters));
+        }
+
+        Ok(query)
+    }
+
+    fn number(key: &str, value: &str) -> Result<i64, String> {
+        value.trim().parse::<i64>().map_err(|_| format!("`{}` must be an integer, got `{}`", key, value))
+    }
+
+    // `-createdAt,name` sorts by `createdAt` descending, then `name` ascending
+    // `-createdAt:nulls_last` also moves NULLs after every other value
+    pub fn sort(value: &str) -> Result<Vec<Order>, String> {
+        let mut orders = vec![];
+
+        for key in value.split(',').map(str::trim).filter(|key| !key.is_empty()) {
+            let (column, nulls) = match key.split_once(':') {
+                Some((column, "nulls_first")) => (column, Some(OrderNulls::First)),
+                Some((column, "nulls_last")) => (column, Some(OrderNulls::Last)),
+                Some(_) => return Err(format!("invalid sort key `{}`, expected `nulls_first` or `nulls_last`", key)),
+                None => (key, None),
+            };
+
+            let (cols, ops) = match column.strip_prefix('-') {
+                Some(cols) => (cols, OrderOps::Desc),
+                None => (column.strip_prefix('+').unwrap_or(column), OrderOps::Asc),
+            };
+
+            if cols.is_empty() {
+                return Err(format!("invalid sort key `{}`", key));
+            }
+
+            orders.push(Order { cols: Some(cols.to_string()), ops: Some(ops), nulls });
+        }
+
+        Ok(orders)
+    }
+
+    // `filter[name]=foo` compares with `Eq`, `filter[name][op]=foo` with the given operator
+    pub fn filter(key: &str, value: &str) -> Result<Filter, String> {
+        let parts = key
+            .strip_prefix("filter[")
+            .and_then(|rest| rest.strip_suffix(']'))
+            .map(|rest| rest.split("][").collect::<Vec<_>>())
+            .filter(|parts| !parts[0].is_empty())
+            .ok_or_else(|| format!("invalid filter `{}`, expected `filter[column]` or `filter[column][operator]`", key))?;
+
+        let ops = match parts.as_slice() {
+            [_] => FilterOps::Eq,
+            [_, op] => serde_json::from_value::<FilterOps>(Value::String(op.to_string()))
+                .map_err(|_| format!("unknown filter operator `{}` in `{}`", op, key))?,
+            _ => return Err(format!("invalid filter `{}`, expected `filter[column]` or `filter[column][operator]`", key)),
+        };
+
+        // List operators take comma separated values
+        let vals = match ops {
+            FilterOps::IsNull | FilterOps::IsNotNull => None,
+            FilterOps::In | FilterOps::NotIn | FilterOps::Between | FilterOps::StartsWith => Some(Value::Array(value
+                .split(',')
+                .map(|item| Value::String(item.to_string()))
+                .collect())),
+            _ => Some(Value::String(value.to_string())),
+        };
+
+        Ok(Filter { cols: Some(parts[0].to_string()), ops: Some(ops), vals })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::PageQuery;
+    use crate::filter::tests::filter;
+    use crate::filter::{FilterNode, FilterOps};
+    use crate::order::{Order, OrderNulls, OrderOps};
+
+    fn pairs(items: &[(&str, &str)]) -> Vec<(String, String)> {
+        items.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn reads_every_key() {
+        let query = PageQuery::from_pairs(pairs(&[
+            ("page", "2"),
+            ("perPage", " 25 "),
+            ("search", "foo bar"),
+            ("sort", "-createdAt:nulls_last,+name"),
+            ("filter[name][like]", "foo"),
+            ("filter[status]", "PAID"),
+            ("after", "WyJhIl0"),
+            ("utm_source", "ignored"),
+        ])).unwrap();
+
+        assert_eq!(query.page, Some(2));
+        assert_eq!(query.per_page, Some(25));
+        assert_eq!(query.search.as_deref(), Some("foo bar"));
+        assert_eq!(query.after.as_deref(), Some("WyJhIl0"));
+        assert_eq!(query.before, None);
+        assert_eq!(query.orders, Some(vec![
+            Order { cols: Some(String::from("createdAt")), ops: Some(OrderOps::Desc), nulls: Some(OrderNulls::Last) },
+            Order { cols: Some(String::from("name")), ops: Some(OrderOps::Asc), nulls: None },
+        ]));
+        assert_eq!(query.filters, Some(FilterNode::And(vec![
+            filter("name", FilterOps::Like, Some(json!("foo"))),
+            filter("status", FilterOps::Eq, Some(json!("PAID"))),
+        ])));
+    }
+
+    #[test]
+    fn leaves_missing_keys_unset() {
+        assert_eq!(PageQuery::from_pairs(vec![]), Ok(PageQuery::default()));
+        assert_eq!(PageQuery::from_pairs(pairs(&[("per_page", "5")])).unwrap().per_page, Some(5));
+    }
+
+    #[test]
+    fn splits_list_operators() {
+        let query = PageQuery::from_pairs(pairs(&[
+            ("filter[id][in]", "1,2"),
+            ("filter[total][between]", "10,20"),
+            ("filter[name][is_null]", "ignored"),
+        ])).unwrap();
+
+        assert_eq!(query.filters, Some(FilterNode::And(vec![
+            filter("id", FilterOps::In, Some(json!(["1", "2"]))),
+            filter("total", FilterOps::Between, Some(json!(["10", "20"]))),
+            filter("name", FilterOps::IsNull, None),
+        ])));
+    }
+
+    #[test]
+    fn rejects_malformed_pairs() {
+        assert_eq!(
+            PageQuery::from_pairs(pairs(&[("page", "x")])),
+            Err(String::from("`page` must be an integer, got `x`"))
+        );
+        assert_eq!(
+            PageQuery::from_pairs(pairs(&[("sort", "-")])),
+            Err(String::from("invalid sort key `-`"))
+        );
+        assert_eq!(
+            PageQuery::from_pairs(pairs(&[("sort", "name:nulls")])),
+            Err(String::from("invalid sort key `name:nulls`, expected `nulls_first` or `nulls_last`"))
+        );
+        assert_eq!(
+            PageQuery::from_pairs(pairs(&[("filter[name][bogus]", "x")])),
+            Err(String::from("unknown filter operator `bogus` in `filter[name][bogus]`"))
+        );
+
+        for key in ["filter", "filter[]", "filter[name", "filter[name][like][x]"] {
+            assert!(PageQuery::from_pairs(pairs(&[(key, "x")])).is_err(), "{}", key);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,27 @@
+// Raised by `Enums` generated `FromStr` and `TryFrom<&str>` for unknown input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownVariant {
+    pub name: &'static str,
+    pub value: String,
+    pub expected: &'static [&'static str],
+}
+
+impl UnknownVariant {
+    pub fn new<T: ToString>(name: &'static str, value: T, expected: &'static [&'static str]) -> Self {
+        Self { name, value: value.to_string(), expected }
+    }
+}
+
+impl std::fmt::Display for UnknownVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown variant `{}` for `{}`, expected one of: {}",
+            self.value,
+            self.name,
+            self.expected.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for UnknownVariant {}
\ No newline at end of file
@@ -0,0 +1,11 @@
+// Stand-in for the internal `errors` library, limited to what the derives use
+pub type Error = actix_web::Error;
+pub type Result<T> = std::result::Result<T, Error>;
+
+pub fn query(e: sqlx::Error) -> Error {
+    actix_web::error::ErrorInternalServerError(e.to_string())
+}
+
+pub fn str_to(message: &str) -> Error {
+    actix_web::error::ErrorBadRequest(message.to_string())
+}
\ No newline at end of file
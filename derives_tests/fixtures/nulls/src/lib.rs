@@ -0,0 +1,58 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// Tri-state field: left out, explicitly NULL or set
+#[derive(Default, Debug, Clone, PartialEq)]
+pub enum Null<T> {
+    #[default]
+    Undefined,
+    Null,
+    Value(T),
+}
+
+pub fn new<T>(value: T) -> Null<T> {
+    Null::Value(value)
+}
+
+impl<T> Null<T> {
+    pub fn take(self) -> Option<T> {
+        match self {
+            Null::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn is_undefined(&self) -> bool {
+        matches!(self, Null::Undefined)
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Null::Null)
+    }
+}
+
+impl<T, E> From<Result<T, E>> for Null<T> {
+    fn from(value: Result<T, E>) -> Self {
+        match value {
+            Ok(value) => Null::Value(value),
+            Err(_) => Null::Undefined,
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for Null<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Null::Value(value) => value.serialize(serializer),
+            _ => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Null<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match Option::<T>::deserialize(deserializer)? {
+            Some(value) => Null::Value(value),
+            None => Null::Null,
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,28 @@
+// Models shared by the integration tests
+use derives::{Enums, PostgreSQL};
+use nulls::Null;
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize, PostgreSQL)]
+#[serde(rename_all = "camelCase")]
+#[psql(table = "invoices", alias = "invoice")]
+pub struct Invoice {
+    #[psql(types = i64, primary_key)]
+    pub id: Null<i64>,
+    #[psql(types = String, required, searchable)]
+    pub number: Null<String>,
+    #[psql(types = String, searchable)]
+    pub customer: Null<String>,
+    #[psql(types = i64)]
+    pub total: Null<i64>,
+    #[psql(types = bool)]
+    pub paid: Null<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Enums)]
+pub enum Status {
+    #[default]
+    None,
+    Active,
+    InActive,
+}
\ No newline at end of file
@@ -0,0 +1,74 @@
+use derives_tests::{InvoiceBind, InvoicePage};
+use serde_json::json;
+
+fn coerce(column: &str, value: serde_json::Value) -> Result<String, String> {
+    InvoiceBind::coerce(column, value)
+        .map(|bind| format!("{:?}", bind))
+        .map_err(|e| e.to_string())
+}
+
+fn filters(filters: serde_json::Value) -> Result<(String, usize), String> {
+    let page = InvoicePage { filters: Some(serde_json::from_value(filters).unwrap()), ..Default::default() };
+
+    page.filters()
+        .map(|conds| conds.to_sql(0))
+        .map(|(sql, _, binds)| (sql, binds))
+        .map_err(|e| e.to_string())
+}
+
+#[test]
+fn coerces_values_to_the_column_type() {
+    assert_eq!(coerce("invoice.total", json!(5)), Ok(String::from("Total(5)")));
+    assert_eq!(coerce("invoice.number", json!("A-1")), Ok(String::from("Number(\"A-1\")")));
+    assert_eq!(coerce("invoice.paid", json!(true)), Ok(String::from("Paid(true)")));
+}
+
+#[test]
+fn retries_text_as_json() {
+    assert_eq!(coerce("invoice.total", json!("5")), Ok(String::from("Total(5)")));
+    assert_eq!(coerce("invoice.paid", json!("false")), Ok(String::from("Paid(false)")));
+    assert_eq!(coerce("invoice.number", json!("5")), Ok(String::from("Number(\"5\")")));
+}
+
+#[test]
+fn rejects_values_of_another_type() {
+    assert!(coerce("invoice.total", json!("five")).unwrap_err().starts_with("invalid value for `invoice.total`"));
+    assert!(coerce("invoice.number", json!(5)).unwrap_err().starts_with("invalid value for `invoice.number`"));
+    assert_eq!(coerce("invoice.missing", json!(5)), Err(String::from("unknown column `invoice.missing`")));
+}
+
+#[test]
+fn builds_patterns() {
+    let pattern = |value| InvoiceBind::pattern("invoice.number", &value, "%", "").map(|bind| format!("{:?}", bind)).map_err(|e| e.to_string());
+
+    assert_eq!(pattern(json!("A")), Ok(String::from("__Pattern(\"%A\")")));
+    assert_eq!(pattern(json!(12)), Ok(String::from("__Pattern(\"%12\")")));
+    assert_eq!(pattern(json!([1])), Err(String::from("invalid pattern for `invoice.number`, expected a string")));
+}
+
+#[test]
+fn casts_non_text_columns_for_patterns() {
+    assert_eq!(InvoiceBind::cast_text("invoice.total"), "invoice.total::text");
+    assert_eq!(InvoiceBind::cast_text("invoice.number"), "invoice.number");
+}
+
+#[test]
+fn binds_filters_with_sequential_placeholders() {
+    assert_eq!(
+        filters(json!([
+            { "cols": "total", "ops": "In", "vals": ["1", 2] },
+            { "cols": "number", "ops": "StartsWith", "vals": ["A", "B"] },
+            { "cols": "total", "ops": "Like", "vals": "9" },
+            { "cols": "paid", "vals": null }
+        ])),
+        Ok((String::from(
+            "WHERE invoice.total IN ($1, $2) AND invoice.number LIKE ANY($3) AND invoice.total::text LIKE $4 AND invoice.paid IS NULL"
+        ), 4))
+    );
+}
+
+#[test]
+fn rejects_filters_that_do_not_fit_the_column() {
+    assert!(filters(json!([{ "cols": "total", "ops": "Gt", "vals": "many" }])).unwrap_err().starts_with("invalid value for `invoice.total`"));
+    assert!(filters(json!([{ "cols": "secret", "vals": 1 }])).unwrap_err().starts_with("unknown filter column `secret`"));
+}
\ No newline at end of file
@@ -0,0 +1,56 @@
+use derives_tests::InvoicePage;
+use serde_json::json;
+
+fn filters(filters: serde_json::Value) -> Result<String, String> {
+    let page = InvoicePage { filters: Some(serde_json::from_value(filters).unwrap()), ..Default::default() };
+
+    page.filters()
+        .map(|conds| conds.to_sql(0).0)
+        .map_err(|e| e.to_string())
+}
+
+#[test]
+fn keeps_the_top_level_and_flat() {
+    assert_eq!(
+        filters(json!([{ "cols": "total", "ops": "Gt", "vals": 1 }, { "cols": "paid", "vals": true }])),
+        Ok(String::from("WHERE invoice.total > $1 AND invoice.paid = $2"))
+    );
+    assert_eq!(filters(json!([])), Ok(String::new()));
+}
+
+#[test]
+fn parenthesizes_nested_groups() {
+    assert_eq!(
+        filters(json!({
+            "or": [
+                { "cols": "number", "vals": "A" },
+                { "not": { "and": [{ "cols": "total", "ops": "Lt", "vals": 0 }, { "cols": "paid", "ops": "IsNull" }] } }
+            ]
+        })),
+        Ok(String::from("WHERE (invoice.number = $1 OR NOT ((invoice.total < $2 AND invoice.paid IS NULL)))"))
+    );
+}
+
+#[test]
+fn renders_empty_groups_like_sql() {
+    assert_eq!(filters(json!({ "or": [] })), Ok(String::from("WHERE FALSE")));
+    assert_eq!(filters(json!({ "not": { "or": [] } })), Ok(String::from("WHERE NOT (FALSE)")));
+    assert_eq!(filters(json!([{ "and": [] }])), Ok(String::from("WHERE TRUE")));
+    assert_eq!(filters(json!({ "not": { "and": [] } })), Ok(String::from("WHERE NOT (TRUE)")));
+    assert_eq!(
+        filters(json!({ "or": [{ "or": [] }, { "cols": "paid", "vals": true }] })),
+        Ok(String::from("WHERE (FALSE OR invoice.paid = $1)"))
+    );
+    assert_eq!(filters(json!([{ "cols": "total", "ops": "In", "vals": [] }])), Ok(String::from("WHERE FALSE")));
+}
+
+#[test]
+fn limits_the_nesting_depth() {
+    let nested = json!({ "not": { "not": { "not": { "not": { "cols": "paid", "ops": "IsNull" } } } } });
+
+    assert_eq!(filters(nested.clone()), Ok(String::from("WHERE NOT (NOT (NOT (NOT (invoice.paid IS NULL))))")));
+    assert_eq!(
+        filters(json!({ "not": nested })),
+        Err(String::from("filters can't be nested deeper than 4 groups"))
+    );
+}
\ No newline at end of file
@@ -0,0 +1,202 @@
+use derives::{Cursor, PageQuery, PostgreSQL};
+use derives_tests::{Invoice, InvoiceList, InvoicePage};
+use nulls::Null;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+// Keyed by the implicit `id` fallback
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize, PostgreSQL)]
+pub struct Voucher {
+    #[psql(types = i64)]
+    pub id: Null<i64>,
+    #[psql(types = String, required)]
+    pub code: Null<String>,
+}
+
+fn page(sort: &str) -> InvoicePage {
+    InvoicePage {
+        orders: Some(PageQuery::sort(sort).unwrap()).filter(|orders| !orders.is_empty()),
+        ..Default::default()
+    }
+}
+
+fn after(sort: &str, values: Vec<Value>) -> InvoicePage {
+    InvoicePage { after: Some(Cursor::new(values).encode()), ..page(sort) }
+}
+
+fn before(sort: &str, values: Vec<Value>) -> InvoicePage {
+    InvoicePage { before: Some(Cursor::new(values).encode()), ..page(sort) }
+}
+
+fn ids(page: &InvoicePage) -> Value {
+    serde_json::to_value(&page.records).unwrap()
+        .as_array()
+        .map(|records| records.iter().map(|record| record["id"].clone()).collect())
+        .unwrap_or_default()
+}
+
+fn keyset(page: &InvoicePage) -> (String, usize) {
+    let (sql, _, binds) = page.keyset().map_err(|e| e.to_string()).unwrap().to_sql(0);
+    (sql, binds)
+}
+
+fn orders(page: &InvoicePage) -> String {
+    page.orders().map_err(|e| e.to_string()).unwrap()
+}
+
+fn invoice(id: i64, total: Option<i64>) -> Invoice {
+    Invoice {
+        id: Null::Value(id),
+        total: total.map(Null::Value).unwrap_or(Null::Null),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn no_cursor_has_no_condition() {
+    assert_eq!(keyset(&page("-total")), (String::new(), 0));
+}
+
+#[test]
+fn defaults_to_the_primary_key() {
+    let page = after("", vec![json!(5)]);
+
+    assert_eq!(keyset(&page), (String::from("WHERE (invoice.id) > ($1)"), 1));
+    assert_eq!(orders(&page), "ORDER BY invoice.id ASC");
+}
+
+#[test]
+fn compares_not_null_keys_as_a_row() {
+    let page = after("number", vec![json!("A-1"), json!(5)]);
+
+    assert_eq!(keyset(&page), (String::from("WHERE (invoice.number, invoice.id) > ($1, $2)"), 2));
+    assert_eq!(orders(&page), "ORDER BY invoice.number ASC, invoice.id ASC");
+}
+
+#[test]
+fn reverses_before_pages() {
+    let page = before("number", vec![json!("A-1"), json!(5)]);
+
+    assert_eq!(keyset(&page), (String::from("WHERE (invoice.number, invoice.id) < ($1, $2)"), 2));
+    assert_eq!(orders(&page), "ORDER BY invoice.number DESC, invoice.id DESC");
+}
+
+#[test]
+fn expands_mixed_directions() {
+    let page = after("number,-id", vec![json!("A"), json!(5)]);
+
+    assert_eq!(
+        keyset(&page),
+        (String::from("WHERE ((invoice.number > $1) OR (invoice.number = $2 AND invoice.id < $3))"), 3)
+    );
+    assert_eq!(orders(&page), "ORDER BY invoice.number ASC, invoice.id DESC");
+}
+
+#[test]
+fn places_nulls_last_when_ascending() {
+    assert_eq!(
+        keyset(&after("total", vec![json!(10), json!(5)])),
+        (String::from("WHERE (((invoice.total > $1 OR invoice.total IS NULL)) OR (invoice.total = $2 AND invoice.id > $3))"), 3)
+    );
+    assert_eq!(
+        keyset(&after("total", vec![Value::Null, json!(5)])),
+        (String::from("WHERE ((invoice.total IS NULL AND invoice.id > $1))"), 1)
+    );
+}
+
+#[test]
+fn places_nulls_first_when_descending() {
+    assert_eq!(
+        keyset(&after("-total", vec![json!(10), json!(5)])),
+        (String::from("WHERE ((invoice.total < $1) OR (invoice.total = $2 AND invoice.id > $3))"), 3)
+    );
+    assert_eq!(
+        keyset(&after("-total", vec![Value::Null, json!(5)])),
+        (String::from("WHERE ((invoice.total IS NOT NULL) OR (invoice.total IS NULL AND invoice.id > $1))"), 1)
+    );
+}
+
+#[test]
+fn follows_requested_null_placement() {
+    let page = after("total:nulls_first", vec![json!(10), json!(5)]);
+
+    assert_eq!(
+        keyset(&page),
+        (String::from("WHERE ((invoice.total > $1) OR (invoice.total = $2 AND invoice.id > $3))"), 3)
+    );
+    assert_eq!(orders(&page), "ORDER BY invoice.total ASC NULLS FIRST, invoice.id ASC");
+}
+
+#[test]
+fn reverses_null_placement_before_a_cursor() {
+    let page = before("-total:nulls_last", vec![json!(10), json!(5)]);
+
+    assert_eq!(
+        keyset(&page),
+        (String::from("WHERE ((invoice.total > $1) OR (invoice.total = $2 AND invoice.id < $3))"), 3)
+    );
+    assert_eq!(orders(&page), "ORDER BY invoice.total ASC NULLS FIRST, invoice.id DESC");
+}
+
+#[test]
+fn rejects_invalid_cursors() {
+    let malformed = InvoicePage { after: Some(String::from("!!")), ..Default::default() };
+    let short = after("number", vec![json!("A")]);
+    let mistyped = after("total", vec![json!("x"), json!(5)]);
+
+    for page in [malformed, short, mistyped] {
+        assert_eq!(page.keyset().map(|_| ()).map_err(|e| e.to_string()), Err(String::from("invalid cursor")));
+    }
+}
+
+#[test]
+fn sets_cursors_only_where_the_walk_goes_on() {
+    let records = |ids: &[i64]| {
+        let mut list = InvoiceList::default();
+        for id in ids {
+            list.push(invoice(*id, Some(*id * 10)));
+        }
+
+        list
+    };
+
+    let first = InvoicePage { per_page: Some(2), ..page("total") }.with_cursors(records(&[1, 2, 3]));
+    assert_eq!(ids(&first), json!([1, 2]));
+    assert_eq!(first.prev_cursor, None);
+    assert_eq!(first.next_cursor, Some(Cursor::new(vec![json!(20), json!(2)]).encode()));
+
+    let last = InvoicePage { per_page: Some(2), after: first.next_cursor.clone(), ..page("total") }.with_cursors(records(&[3]));
+    assert_eq!(last.prev_cursor, Some(Cursor::new(vec![json!(30), json!(3)]).encode()));
+    assert_eq!(last.next_cursor, None);
+
+    let response = last.response();
+    assert_eq!((response.has_prev, response.has_next), (Some(true), Some(false)));
+}
+
+#[test]
+fn restores_the_order_of_before_pages() {
+    let mut list = InvoiceList::default();
+    for id in [4, 3, 2] {
+        list.push(invoice(id, None));
+    }
+
+    let page = InvoicePage { per_page: Some(2), ..before("", vec![json!(5)]) }.with_cursors(list);
+    assert_eq!(ids(&page), json!([3, 4]));
+    assert_eq!(page.prev_cursor, Some(Cursor::new(vec![json!(3)]).encode()));
+    assert_eq!(page.next_cursor, Some(Cursor::new(vec![json!(4)]).encode()));
+}
+
+#[test]
+fn treats_the_implicit_id_as_a_not_null_key() {
+    let id = Voucher::COLUMNS.iter().find(|column| column.field == "id").unwrap();
+    assert!(id.key && !id.nullable);
+
+    let page = VoucherPage {
+        orders: Some(PageQuery::sort("code").unwrap()),
+        after: Some(Cursor::new(vec![json!("A-1"), json!(5)]).encode()),
+        ..Default::default()
+    };
+    let (sql, _, binds) = page.keyset().map_err(|e| e.to_string()).unwrap().to_sql(0);
+
+    assert_eq!((sql, binds), (String::from("WHERE (voucher.code, voucher.id) > ($1, $2)"), 2));
+}
\ No newline at end of file
@@ -0,0 +1,52 @@
+use derives::Enums;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Enums)]
+#[enums(rename_all = "kebab-case", pg_type = "billing.channel_kind")]
+pub enum Channel {
+    #[default]
+    WebHook,
+    EmailDigest,
+    #[enums(rename = "SMS{legacy}", alias = "text", alias = "Txt")]
+    ShortMessage,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Enums)]
+#[enums(rename_all = "camelCase")]
+pub enum Mode {
+    ReadOnly,
+    ReadWrite,
+}
+
+#[test]
+fn applies_the_container_casing() {
+    assert_eq!(Channel::EmailDigest.to_string(), "email-digest");
+    assert_eq!(Mode::ReadWrite.as_str(), "readWrite");
+    assert_eq!(serde_json::to_string(&Mode::ReadOnly).unwrap(), "\"readOnly\"");
+}
+
+#[test]
+fn writes_renamed_labels_exactly() {
+    assert_eq!(Channel::ShortMessage.to_string(), "SMS{legacy}");
+    assert_eq!(serde_json::to_string(&Channel::ShortMessage).unwrap(), "\"SMS{legacy}\"");
+    assert_eq!(
+        Channel::CREATE_TYPE_SQL,
+        "CREATE TYPE billing.channel_kind AS ENUM ('web-hook', 'email-digest', 'SMS{legacy}')"
+    );
+}
+
+#[test]
+fn accepts_aliases_on_input_only() {
+    assert_eq!("TEXT".parse::<Channel>(), Ok(Channel::ShortMessage));
+    assert_eq!("txt".parse::<Channel>(), Ok(Channel::ShortMessage));
+    assert_eq!("sms{LEGACY}".parse::<Channel>(), Ok(Channel::ShortMessage));
+    assert_eq!(serde_json::from_str::<Channel>("\"Web-Hook\"").unwrap(), Channel::WebHook);
+}
+
+#[test]
+fn lists_only_labels_as_expected() {
+    assert_eq!(
+        "sms".parse::<Channel>().unwrap_err().to_string(),
+        "unknown variant `sms` for `Channel`, expected one of: web-hook, email-digest, SMS{legacy}"
+    );
+    assert_eq!("web_hook".parse::<Channel>().map_err(|e| e.value), Err(String::from("web_hook")));
+}
\ No newline at end of file
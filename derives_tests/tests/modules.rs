@@ -0,0 +1,34 @@
+use derives::PostgreSQL;
+use nulls::Null;
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize, PostgreSQL)]
+pub struct Type {
+    #[psql(types = i64)]
+    pub id: Null<i64>,
+    #[psql(types = String)]
+    pub name: Null<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize, PostgreSQL)]
+pub struct Match {
+    #[psql(types = i64)]
+    pub id: Null<i64>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize, PostgreSQL)]
+pub struct Super {
+    #[psql(types = i64)]
+    pub id: Null<i64>,
+}
+
+#[test]
+fn keywords_become_raw_modules() {
+    assert_eq!(r#type::alias::ALL, "type.id AS type_id,type.name AS type_name");
+    assert_eq!(r#match::alias::ALL, "match.id AS match_id");
+}
+
+#[test]
+fn path_keywords_get_an_underscore() {
+    assert_eq!(super_::alias::ALL, "super.id AS super_id");
+}
\ No newline at end of file
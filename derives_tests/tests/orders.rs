@@ -0,0 +1,98 @@
+use derives::{Order, OrderNulls, OrderOps, PageQuery};
+use derives::PostgreSQL;
+use derives_tests::InvoicePage;
+use nulls::Null;
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize, PostgreSQL)]
+#[psql(default_order = "issued_at desc nulls last, number")]
+pub struct Receipt {
+    #[psql(types = i64)]
+    pub id: Null<i64>,
+    #[psql(types = String)]
+    pub number: Null<String>,
+    #[psql(types = i64, column = "issuedAt")]
+    pub issued_at: Null<i64>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize, PostgreSQL)]
+#[psql(default_order = "issuedAt DESC, id")]
+pub struct Refund {
+    #[psql(types = i64)]
+    pub id: Null<i64>,
+    #[psql(types = i64, column = "issuedAt")]
+    pub issued_at: Null<i64>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize, PostgreSQL)]
+#[psql(default_order = "amount nulls first")]
+pub struct Credit {
+    #[psql(types = i64, primary_key)]
+    pub code: Null<i64>,
+    #[psql(types = i64)]
+    pub amount: Null<i64>,
+}
+
+fn sorted(sort: &str) -> Result<String, String> {
+    let page = InvoicePage { orders: Some(PageQuery::sort(sort).unwrap()), ..Default::default() };
+
+    page.orders().map_err(|e| e.to_string())
+}
+
+#[test]
+fn adds_nulls_only_for_the_non_default_end() {
+    assert_eq!(sorted("total"), Ok(String::from("ORDER BY invoice.total ASC, invoice.id ASC")));
+    assert_eq!(sorted("-total"), Ok(String::from("ORDER BY invoice.total DESC, invoice.id ASC")));
+    assert_eq!(sorted("total:nulls_last"), Ok(String::from("ORDER BY invoice.total ASC, invoice.id ASC")));
+    assert_eq!(sorted("total:nulls_first"), Ok(String::from("ORDER BY invoice.total ASC NULLS FIRST, invoice.id ASC")));
+    assert_eq!(sorted("-total:nulls_first"), Ok(String::from("ORDER BY invoice.total DESC, invoice.id ASC")));
+    assert_eq!(sorted("-total:nulls_last"), Ok(String::from("ORDER BY invoice.total DESC NULLS LAST, invoice.id ASC")));
+}
+
+#[test]
+fn appends_the_primary_key_once() {
+    assert_eq!(sorted("-id"), Ok(String::from("ORDER BY invoice.id DESC")));
+    assert_eq!(sorted("number,id"), Ok(String::from("ORDER BY invoice.number ASC, invoice.id ASC")));
+}
+
+#[test]
+fn drops_repeated_keys() {
+    assert_eq!(sorted("total,-total"), Ok(String::from("ORDER BY invoice.total ASC, invoice.id ASC")));
+}
+
+#[test]
+fn accepts_json_orders() {
+    let page = InvoicePage {
+        orders: Some(vec![Order { cols: Some(String::from("total")), ops: Some(OrderOps::Desc), nulls: Some(OrderNulls::Last) }]),
+        ..Default::default()
+    };
+
+    assert_eq!(page.orders().map_err(|e| e.to_string()), Ok(String::from("ORDER BY invoice.total DESC NULLS LAST, invoice.id ASC")));
+}
+
+#[test]
+fn rejects_unknown_and_excess_orders() {
+    assert!(sorted("secret").unwrap_err().starts_with("unknown sort column `secret`"));
+    assert_eq!(sorted("number,total,paid,customer,id"), Err(String::from("at most 4 orders are allowed")));
+}
+
+#[test]
+fn falls_back_to_the_default_order() {
+    assert_eq!(
+        ReceiptPage::default().orders().map_err(|e| e.to_string()),
+        Ok(String::from("ORDER BY receipt.\"issuedAt\" DESC NULLS LAST, receipt.number ASC, receipt.id ASC"))
+    );
+    assert_eq!(
+        RefundPage::default().orders().map_err(|e| e.to_string()),
+        Ok(String::from("ORDER BY refund.\"issuedAt\" DESC, refund.id ASC"))
+    );
+    assert_eq!(
+        CreditPage::default().orders().map_err(|e| e.to_string()),
+        Ok(String::from("ORDER BY credit.amount ASC NULLS FIRST, credit.code ASC"))
+    );
+}
+
+#[test]
+fn sorts_by_the_primary_key_without_a_default_order() {
+    assert_eq!(InvoicePage::default().orders().map_err(|e| e.to_string()), Ok(String::from("ORDER BY invoice.id ASC")));
+}
\ No newline at end of file
@@ -0,0 +1,42 @@
+use derives::{Enums, UnknownVariant};
+use derives_tests::Status;
+
+// No fallback variant, so only the fallible conversions exist
+#[derive(Debug, Clone, Copy, PartialEq, Enums)]
+pub enum Priority {
+    Low,
+    High,
+}
+
+#[test]
+fn parses_labels_case_insensitively() {
+    assert_eq!("IN_ACTIVE".parse::<Status>(), Ok(Status::InActive));
+    assert_eq!("in_active".parse::<Status>(), Ok(Status::InActive));
+    assert_eq!("High".parse::<Priority>(), Ok(Priority::High));
+    assert_eq!(Priority::try_from("low"), Ok(Priority::Low));
+}
+
+#[test]
+fn reports_unknown_variants() {
+    let error = "urgent".parse::<Priority>().unwrap_err();
+
+    assert_eq!(error, UnknownVariant::new("Priority", "urgent", &["LOW", "HIGH"]));
+    assert_eq!(error.to_string(), "unknown variant `urgent` for `Priority`, expected one of: LOW, HIGH");
+}
+
+#[test]
+fn falls_back_to_the_default_variant() {
+    assert_eq!(Status::from(String::from("active")), Status::Active);
+    assert_eq!(Status::from(String::from("urgent")), Status::None);
+    assert_eq!(Status::from(&String::from("IN_ACTIVE")), Status::InActive);
+    assert_eq!(Status::from(None::<String>), Status::None);
+}
+
+#[test]
+fn rejects_unknown_input_through_serde() {
+    assert_eq!(serde_json::from_str::<Priority>("\"high\"").unwrap(), Priority::High);
+    assert_eq!(
+        serde_json::from_str::<Priority>("\"urgent\"").unwrap_err().to_string(),
+        "unknown variant `urgent`, expected `LOW` or `HIGH`"
+    );
+}
\ No newline at end of file
@@ -0,0 +1,79 @@
+use change_case::{camel_case, param_case, pascal_case, snake_case};
+use deluxe::extract_attributes;
+use syn::{DeriveInput, Ident, Variant};
+
+// Set container Attr struct
+#[derive(deluxe::ExtractAttributes, Default)]
+#[deluxe(attributes(enums), default)]
+pub struct ContainerAttrs {
+    pub pg_type: Option<String>,
+    pub rename_all: Option<String>
+}
+
+// Set variant Attr struct
+#[derive(deluxe::ExtractAttributes, Default)]
+#[deluxe(attributes(enums), default)]
+pub struct VariantAttrs {
+    pub rename: Option<String>,
+    #[deluxe(append)]
+    pub alias: Vec<String>
+}
+
+// Extract container attributes
+pub fn extract_container(ast: &mut DeriveInput) -> deluxe::Result<ContainerAttrs> {
+    extract_attributes(ast)
+}
+
+// Extract variant attributes
+pub fn extract_variant(variant: &mut Variant) -> deluxe::Result<VariantAttrs> {
+    extract_attributes(variant)
+}
+
+// Label of a variant under the container casing, SCREAMING_SNAKE_CASE by default
+pub fn rename_variant(variant: &Ident, rename_all: Option<&str>) -> Option<String> {
+    let name = variant.to_string();
+
+    match rename_all {
+        None | Some("SCREAMING_SNAKE_CASE") => Some(snake_case(&name).to_uppercase()),
+        Some("snake_case") => Some(snake_case(&name)),
+        Some("kebab-case") => Some(param_case(&name)),
+        Some("SCREAMING-KEBAB-CASE") => Some(param_case(&name).to_uppercase()),
+        Some("camelCase") => Some(camel_case(&name)),
+        Some("PascalCase") => Some(pascal_case(&name)),
+        Some("lowercase") => Some(name.to_lowercase()),
+        Some("UPPERCASE") => Some(name.to_uppercase()),
+        _ => None
+    }
+}
+
+// PostgreSQL reserved key words that can't be used as bare type names, kept in step with postgresql-derive
+const RESERVED: &[&str] = &[
+    "all", "analyse", "analyze", "and", "any", "array", "as", "asc", "asymmetric",
+    "authorization", "binary", "both", "case", "cast", "check", "collate", "collation",
+    "column", "concurrently", "constraint", "create", "cross", "current_catalog",
+    "current_date", "current_role", "current_schema", "current_time", "current_timestamp",
+    "current_user", "default", "deferrable", "desc", "distinct", "do", "else", "end",
+    "except", "false", "fetch", "for", "foreign", "freeze", "from", "full", "grant",
+    "group", "having", "ilike", "in", "initially", "inner", "intersect", "into", "is",
+    "isnull", "join", "lateral", "leading", "left", "like", "limit", "localtime",
+    "localtimestamp", "natural", "not", "notnull", "null", "offset", "on", "only", "or",
+    "order", "outer", "overlaps", "placing", "primary", "references", "returning", "right",
+    "select", "session_user", "similar", "some", "symmetric", "system_user", "table",
+    "tablesample", "then", "to", "trailing", "true", "union", "unique", "user", "using",
+    "variadic", "verbose", "when", "where", "window", "with"
+];
+
+// Array type of a `name` or `schema.name` enum type, `None` unless every part is a plain lowercase, non-reserved identifier
+pub fn pg_array_type(pg_type: &str) -> Option<String> {
+    let is_identifier = |part: &str| {
+        part.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+            && part.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+            && !RESERVED.contains(&part)
+    };
+
+    match pg_type.split('.').collect::<Vec<_>>().as_slice() {
+        [name] if is_identifier(name) => Some(format!("_{}", name)),
+        [schema, name] if is_identifier(schema) && is_identifier(name) => Some(format!("{}._{}", schema, name)),
+        _ => None
+    }
+}
\ No newline at end of file
@@ -1,11 +1,30 @@
+mod extractors;
+
 use change_case::snake_case;
 use proc_macro::TokenStream;
 use quote::format_ident;
 use syn::{parse_macro_input, Data, DeriveInput};
 
-#[proc_macro_derive(Enums)]
+#[proc_macro_derive(Enums, attributes(enums))]
 pub fn derive_enum_iter(input: TokenStream) -> TokenStream {
-    let ast = parse_macro_input!(input as DeriveInput);
+    let mut ast = parse_macro_input!(input as DeriveInput);
+    let container = match extractors::extract_container(&mut ast) {
+        Ok(container) => container,
+        Err(e) => return e.into_compile_error().into()
+    };
+
+    let variant_attrs = match &mut ast.data {
+        Data::Enum(data) => data.variants
+            .iter_mut()
+            .map(extractors::extract_variant)
+            .collect::<Result<Vec<_>, _>>(),
+        _ => panic!("This derive is only applicable under enum types.")
+    };
+
+    let variant_attrs = match variant_attrs {
+        Ok(variant_attrs) => variant_attrs,
+        Err(e) => return e.into_compile_error().into()
+    };
 
     let ident = &ast.ident;
     let variants = match &ast.data {
@@ -34,13 +53,13 @@ pub fn derive_enum_iter(input: TokenStream) -> TokenStream {
 
 
     let mut token = quote::quote!{};
-    let mut default_from_conversion = quote::quote!{};
 
     let mut checkers = vec![];
-    let mut variant_name_snake_lower = vec![];
-    let mut variant_name_snake_upper = vec![];
+    let mut labels = vec![];
+    let mut inputs = vec![];
+    let mut known = vec![];
 
-    for variant in variants.clone() {
+    for (variant, attrs) in variants.iter().zip(variant_attrs) {
         let function_name = format_ident!("is_{}", snake_case(&variant.to_string()));
         checkers.push(quote::quote! {
             pub fn #function_name(&self) -> bool {
@@ -48,60 +67,270 @@ pub fn derive_enum_iter(input: TokenStream) -> TokenStream {
             }
         });
 
-        if let Some(default_variant) = default_variant {
-            default_from_conversion.extend(quote::quote! {
-                _ => Self::#default_variant,
-            });
+        let label = match attrs.rename {
+            Some(rename) => rename,
+            None => match extractors::rename_variant(variant, container.rename_all.as_deref()) {
+                Some(label) => label,
+                None => return syn::Error::new_spanned(
+                    ident,
+                    "unknown rename_all, expected one of: lowercase, UPPERCASE, PascalCase, camelCase, \
+                    snake_case, SCREAMING_SNAKE_CASE, kebab-case, SCREAMING-KEBAB-CASE"
+                ).into_compile_error().into()
+            }
         };
 
-        variant_name_snake_lower.push(snake_case(&variant.to_string()));
-        variant_name_snake_upper.push(snake_case(&variant.to_string()).to_uppercase());
+        // Input is matched case-insensitively, aliases are accepted but never written
+        let mut accepted = vec![];
+        for input in std::iter::once(&label).chain(attrs.alias.iter()).map(|input| input.to_lowercase()) {
+            if known.contains(&input) {
+                return syn::Error::new_spanned(variant, format!("label `{}` is already used by another variant", input))
+                    .into_compile_error()
+                    .into();
+            }
+
+            known.push(input.clone());
+            accepted.push(input);
+        }
+
+        labels.push(label);
+        inputs.push(accepted);
     }
 
+    // Lossy conversions are only generated when a `#[default]` variant can absorb unknown input
+    let default_conversions = match default_variant {
+        Some(default_variant) => quote::quote! {
+            impl From<String> for #ident {
+                fn from(value: String) -> Self {
+                    value.parse().unwrap_or(Self::#default_variant)
+                }
+            }
+
+            impl From<&String> for #ident {
+                fn from(value: &String) -> Self {
+                    value.parse().unwrap_or(Self::#default_variant)
+                }
+            }
+
+            impl From<Option<String>> for #ident {
+                fn from(value: Option<String>) -> Self {
+                    match value {
+                        Some(value) => Self::from(value),
+                        None => Self::#default_variant,
+                    }
+                }
+            }
+        },
+        None => quote::quote! {}
+    };
+
+    // Stored as text unless a native PostgreSQL enum type is named
+    let sqlx_types = match container.pg_type.clone() {
+        Some(pg_type) => {
+            let array_type = match extractors::pg_array_type(&pg_type) {
+                Some(array_type) => array_type,
+                None => return syn::Error::new_spanned(
+                    ident,
+                    "pg_type must be a lowercase, non-reserved identifier, optionally qualified as `schema.name`"
+                ).into_compile_error().into()
+            };
+
+            // Result columns report the type without its schema
+            let bare_type = pg_type.rsplit('.').next().unwrap_or_default().to_string();
+            let bare_array_type = format!("_{}", bare_type);
+
+            // sqlx 0.8 reports arrays of custom types by their element, e.g. `billing.channel_kind[]`
+            let (array_type_info, bare_array_type_info) = if cfg!(feature = "sqlx-0-8") {
+                (
+                    quote::quote! { sqlx::postgres::PgTypeInfo::array_of(#pg_type) },
+                    quote::quote! { sqlx::postgres::PgTypeInfo::array_of(#bare_type) }
+                )
+            } else {
+                (
+                    quote::quote! { sqlx::postgres::PgTypeInfo::with_name(#array_type) },
+                    quote::quote! { sqlx::postgres::PgTypeInfo::with_name(#bare_array_type) }
+                )
+            };
+
+            let quoted = labels
+                .iter()
+                .map(|label| format!("'{}'", label.replace('\'', "''")))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let create_type = format!("CREATE TYPE {} AS ENUM ({})", pg_type, quoted);
+
+            quote::quote! {
+                impl #ident {
+                    pub const PG_TYPE: &'static str = #pg_type;
+                    pub const CREATE_TYPE_SQL: &'static str = #create_type;
+
+                    // Statements adding the missing labels, each placed next to its declared neighbour
+                    pub fn alter_type_sql(existing: &[&str]) -> Vec<String> {
+                        let labels = [#( #labels ),*];
+                        let mut known = existing.iter().map(|label| label.to_string()).collect::<Vec<_>>();
+                        let mut statements = vec![];
+
+                        for (index, label) in labels.iter().enumerate() {
+                            if known.iter().any(|k| k == label) {
+                                continue;
+                            }
+
+                            let quote = |label: &str| format!("'{}'", label.replace('\'', "''"));
+                            let after = labels[..index].iter().rev().find(|prev| known.iter().any(|k| k == *prev));
+                            let before = labels[index + 1..].iter().find(|next| known.iter().any(|k| k == *next));
+
+                            let position = match (after, before) {
+                                (Some(after), _) => format!(" AFTER {}", quote(after)),
+                                (None, Some(before)) => format!(" BEFORE {}", quote(before)),
+                                (None, None) => String::new(),
+                            };
+
+                            statements.push(format!(
+                                "ALTER TYPE {} ADD VALUE IF NOT EXISTS {}{}",
+                                Self::PG_TYPE, quote(label), position
+                            ));
+
+                            known.push(label.to_string());
+                        }
+
+                        statements
+                    }
+                }
+
+                impl sqlx::Type<sqlx::Postgres> for #ident {
+                    fn type_info() -> sqlx::postgres::PgTypeInfo {
+                        sqlx::postgres::PgTypeInfo::with_name(#pg_type)
+                    }
+
+                    fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+                        *ty == Self::type_info()
+                            || *ty == sqlx::postgres::PgTypeInfo::with_name(#bare_type)
+                            || <String as sqlx::Type<sqlx::Postgres>>::compatible(ty)
+                    }
+                }
+
+                impl sqlx::postgres::PgHasArrayType for #ident {
+                    fn array_type_info() -> sqlx::postgres::PgTypeInfo {
+                        #array_type_info
+                    }
+
+                    fn array_compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+                        *ty == Self::array_type_info()
+                            || *ty == #bare_array_type_info
+                            || <String as sqlx::postgres::PgHasArrayType>::array_compatible(ty)
+                    }
+                }
+            }
+        },
+        None => quote::quote! {
+            impl sqlx::Type<sqlx::Postgres> for #ident {
+                fn type_info() -> sqlx::postgres::PgTypeInfo {
+                    <String as sqlx::Type<sqlx::Postgres>>::type_info()
+                }
+
+                fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+                    <String as sqlx::Type<sqlx::Postgres>>::compatible(ty)
+                }
+            }
+
+            impl sqlx::postgres::PgHasArrayType for #ident {
+                fn array_type_info() -> sqlx::postgres::PgTypeInfo {
+                    <String as sqlx::postgres::PgHasArrayType>::array_type_info()
+                }
+
+                fn array_compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+                    <String as sqlx::postgres::PgHasArrayType>::array_compatible(ty)
+                }
+            }
+        }
+    };
+
+    // sqlx 0.8 made encoding fallible
+    let encoded = if cfg!(feature = "sqlx-0-8") {
+        quote::quote! { Result<sqlx::encode::IsNull, sqlx::error::BoxDynError> }
+    } else {
+        quote::quote! { sqlx::encode::IsNull }
+    };
+
+    let count = variants.len();
+    let indexes = (0..count).collect::<Vec<_>>();
+
     token.extend(quote::quote! {
         impl #ident {
             #(#checkers)*
+
+            // Every variant in declaration order
+            pub const ALL: &'static [Self] = &[#( Self::#variants ),*];
+            pub const COUNT: usize = #count;
+
+            pub const fn as_str(&self) -> &'static str {
+                match self {
+                    #( Self::#variants => #labels, )*
+                }
+            }
+
+            pub fn iter() -> std::slice::Iter<'static, Self> {
+                Self::ALL.iter()
+            }
+
+            pub const fn index(&self) -> usize {
+                match self {
+                    #( Self::#variants => #indexes, )*
+                }
+            }
+
+            pub const fn from_index(index: usize) -> Option<Self> {
+                match index {
+                    #( #indexes => Some(Self::#variants), )*
+                    _ => None
+                }
+            }
+
+            // Neighbouring variants, `None` past either end
+            pub const fn next(&self) -> Option<Self> {
+                Self::from_index(self.index() + 1)
+            }
+
+            pub const fn prev(&self) -> Option<Self> {
+                match self.index() {
+                    0 => None,
+                    index => Self::from_index(index - 1)
+                }
+            }
         }
 
         // Conversions
         // ____________________________________________
-        impl From<String> for #ident {
-            fn from(value: String) -> Self {
+        impl std::str::FromStr for #ident {
+            type Err = derives::UnknownVariant;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
                 match value.to_lowercase().as_str() {
-                    #(#variant_name_snake_lower => Self::#variants,)*
-                    #default_from_conversion
+                    #( #( #inputs )|* => Ok(Self::#variants), )*
+                    _ => Err(derives::UnknownVariant::new(
+                        stringify!(#ident),
+                        value,
+                        &[ #( #labels, )* ],
+                    )),
                 }
             }
         }
 
-        impl From<&String> for #ident {
-            fn from(value: &String) -> Self {
-                Self::from(value.to_string())
-            }
-        }
+        impl TryFrom<&str> for #ident {
+            type Error = derives::UnknownVariant;
 
-        impl From<&str> for #ident {
-            fn from(value: &str) -> Self {
-                Self::from(value.to_string())
+            fn try_from(value: &str) -> Result<Self, Self::Error> {
+                value.parse()
             }
         }
 
-        impl From<Option<String>> for #ident {
-            fn from(value: Option<String>) -> Self {
-                match value {
-                    Some(value) => Self::from(value),
-                    None => Self::None,
-                }
-            }
-        }
+        #default_conversions
 
         // Display
         // ____________________________________________
         impl std::fmt::Display for #ident {
             fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-                match self {
-                    #(#ident::#variants => write!(f, #variant_name_snake_upper),)*
-                }
+                f.write_str(self.as_str())
             }
         }
 
@@ -114,13 +343,9 @@ pub fn derive_enum_iter(input: TokenStream) -> TokenStream {
             {
                 let variant = String::deserialize(deserializer)?;
 
-                match variant.to_lowercase().as_str() {
-                    #( #variant_name_snake_lower => Ok(Self::#variants), )*
-                    _ => Err(serde::de::Error::unknown_variant(
-                        &variant,
-                        &[  #( #variant_name_snake_upper, )* ],
-                    )),
-                }
+                variant.parse().map_err(|e: derives::UnknownVariant| {
+                    serde::de::Error::unknown_variant(&e.value, e.expected)
+                })
             }
         }
 
@@ -128,11 +353,25 @@ pub fn derive_enum_iter(input: TokenStream) -> TokenStream {
         // ____________________________________________
         impl serde::Serialize for #ident {
             fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
-                let variant_str = match self {
-                    #( Self::#variants => #variant_name_snake_upper, )*
-                };
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        // sqlx
+        // ____________________________________________
+        #sqlx_types
+
+        impl<'q> sqlx::Encode<'q, sqlx::Postgres> for #ident {
+            fn encode_by_ref(&self, buf: &mut sqlx::postgres::PgArgumentBuffer) -> #encoded {
+                <&str as sqlx::Encode<'q, sqlx::Postgres>>::encode(self.as_str(), buf)
+            }
+        }
+
+        impl<'r> sqlx::Decode<'r, sqlx::Postgres> for #ident {
+            fn decode(value: sqlx::postgres::PgValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+                let variant = <&str as sqlx::Decode<'r, sqlx::Postgres>>::decode(value)?;
 
-                serializer.serialize_str(variant_str)
+                Ok(variant.parse::<Self>()?)
             }
         }
     });
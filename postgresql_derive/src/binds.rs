@@ -0,0 +1,174 @@
+use proc_macro2::TokenStream;
+use syn::{Ident, Type};
+
+use crate::extractors;
+
+pub fn stream(
+    node: &Ident,
+    columns: &[Ident],
+    fields: &[Ident],
+    types: &[Type]
+) -> TokenStream {
+    let module = extractors::module_name(node);
+    let bind = quote::format_ident!("{}Bind", node);
+    let segment = quote::format_ident!("{}Segment", node);
+    let conditions = quote::format_ident!("{}Where", node);
+
+    // Field variants are PascalCase, which never starts with `__`, so the pattern variants can't collide
+    let variants = fields
+        .iter()
+        .map(|field| quote::format_ident!("{}", stringcase::pascal_case(&field.to_string())))
+        .collect::<Vec<_>>();
+
+    // LIKE patterns are text, other column types are cast before matching
+    let casts = columns
+        .iter()
+        .zip(types)
+        .filter(|(_, ty)| extractors::type_to_string(ty) != "String")
+        .map(|(column, _)| column.clone())
+        .collect::<Vec<_>>();
+
+    quote::quote! {
+        #[derive(Debug, Clone)]
+        pub enum #bind {
+            #( #variants(#types), )*
+            __Pattern(String),
+            __Patterns(Vec<String>),
+        }
+
+        impl #bind {
+            pub fn coerce(column: &str, value: serde_json::Value) -> errors::Result<Self> {
+                match column {
+                    #(
+                        #module::tables::#columns => {
+                            // Query strings carry every value as text, so retry it as JSON
+                            let parsed = serde_json::from_value::<#types>(value.clone()).or_else(|e| match &value {
+                                serde_json::Value::String(text) => serde_json::from_str::<#types>(text).map_err(|_| e),
+                                _ => Err(e)
+                            });
+
+                            parsed
+                                .map(Self::#variants)
+                                .map_err(|e| errors::str_to(&format!("invalid value for `{}`: {}", column, e)))
+                        },
+                    )*
+                    _ => Err(errors::str_to(&format!("unknown column `{}`", column)))
+                }
+            }
+
+            pub fn pattern(column: &str, value: &serde_json::Value, left: &str, right: &str) -> errors::Result<Self> {
+                match value {
+                    serde_json::Value::String(d) => Ok(Self::__Pattern(format!("{}{}{}", left, d, right))),
+                    serde_json::Value::Number(d) => Ok(Self::__Pattern(format!("{}{}{}", left, d, right))),
+                    _ => Err(errors::str_to(&format!("invalid pattern for `{}`, expected a string", column)))
+                }
+            }
+
+            pub fn cast_text(column: &str) -> String {
+                match column {
+                    #( #module::tables::#casts => format!("{}::text", column), )*
+                    _ => column.to_string()
+                }
+            }
+
+            pub fn push_bind(self, builder: &mut sqlx::QueryBuilder<'_, sqlx::Postgres>) {
+                match self {
+                    #( Self::#variants(value) => { builder.push_bind(value); }, )*
+                    Self::__Pattern(value) => { builder.push_bind(value); },
+                    Self::__Patterns(value) => { builder.push_bind(value); },
+                }
+            }
+
+            pub fn add(self, arguments: &mut sqlx::postgres::PgArguments) {
+                use sqlx::Arguments;
+
+                match self {
+                    #( Self::#variants(value) => { let _ = arguments.add(value); }, )*
+                    Self::__Pattern(value) => { let _ = arguments.add(value); },
+                    Self::__Patterns(value) => { let _ = arguments.add(value); },
+                }
+            }
+        }
+
+        #[derive(Debug, Clone)]
+        pub enum #segment {
+            Sql(String),
+            Bind(#bind),
+        }
+
+        // Conditions are combined with AND, each one is a run of SQL and binds
+        #[derive(Default, Debug, Clone)]
+        pub struct #conditions {
+            pub conditions: Vec<Vec<#segment>>,
+        }
+
+        impl #conditions {
+            pub fn is_empty(&self) -> bool {
+                self.conditions.is_empty()
+            }
+
+            pub fn and(&mut self, condition: Vec<#segment>) {
+                if !condition.is_empty() {
+                    self.conditions.push(condition);
+                }
+            }
+
+            pub fn extend(&mut self, other: Self) {
+                self.conditions.extend(other.conditions);
+            }
+
+            pub fn apply(&self, builder: &mut sqlx::QueryBuilder<'_, sqlx::Postgres>) {
+                self.push_conditions(builder, " WHERE ");
+            }
+
+            // Continues a builder that already holds a WHERE clause
+            pub fn apply_and(&self, builder: &mut sqlx::QueryBuilder<'_, sqlx::Postgres>) {
+                self.push_conditions(builder, " AND ");
+            }
+
+            fn push_conditions(&self, builder: &mut sqlx::QueryBuilder<'_, sqlx::Postgres>, first: &str) {
+                for (index, condition) in self.conditions.iter().enumerate() {
+                    builder.push(match index {
+                        0 => first,
+                        _ => " AND "
+                    });
+
+                    for segment in condition {
+                        match segment.clone() {
+                            #segment::Sql(sql) => { builder.push(sql); },
+                            #segment::Bind(bind) => bind.push_bind(builder),
+                        }
+                    }
+                }
+            }
+
+            pub fn to_sql(&self, start_index: usize) -> (String, sqlx::postgres::PgArguments, usize) {
+                let mut arguments = sqlx::postgres::PgArguments::default();
+                let mut conds = vec![];
+                let mut idx = start_index;
+
+                for condition in self.conditions.iter() {
+                    let mut sql = String::new();
+
+                    for segment in condition {
+                        match segment.clone() {
+                            #segment::Sql(d) => sql.push_str(&d),
+                            #segment::Bind(bind) => {
+                                idx += 1;
+                                sql.push_str(&format!("${}", idx));
+                                bind.add(&mut arguments);
+                            }
+                        }
+                    }
+
+                    conds.push(sql);
+                }
+
+                match conds.is_empty() {
+                    true => (String::new(), arguments, idx),
+                    false => (format!("WHERE {}", conds.join(" AND ")), arguments, idx)
+                }
+            }
+        }
+    }
+}
\ No newline at end of file
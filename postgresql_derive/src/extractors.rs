@@ -1,4 +1,3 @@
-use std::collections::HashMap;
 use deluxe::extract_attributes;
 use regex::Regex;
 use syn::{DeriveInput, Data, Fields, Ident, Type, parse_str};
@@ -9,17 +8,60 @@ use syn::{DeriveInput, Data, Fields, Ident, Type, parse_str};
 #[allow(dead_code)]
 pub struct Attrs {
     pub types: Type,
-    pub props: Option<Type>
+    pub props: Option<Type>,
+    pub column: Option<String>,
+    #[deluxe(default)]
+    pub primary_key: bool,
+    #[deluxe(default)]
+    pub required: bool,
+    #[deluxe(default)]
+    pub optional: bool,
+    #[deluxe(default)]
+    pub filterable: bool,
+    #[deluxe(default)]
+    pub sortable: bool,
+    #[deluxe(default)]
+    pub searchable: bool,
+    #[deluxe(default)]
+    pub encrypted: bool
 }
 
-// Extract attributes
-pub fn extract_attrs(ast: &mut DeriveInput) -> deluxe::Result<HashMap<Ident, Attrs>> {
-    let mut attrs = HashMap::new();
+// Set container Attr struct
+#[derive(deluxe::ExtractAttributes, Default)]
+#[deluxe(attributes(psql), default)]
+pub struct ContainerAttrs {
+    pub table: Option<String>,
+    pub schema: Option<String>,
+    pub alias: Option<String>,
+    pub from_row: Option<String>,
+    pub search: Option<String>,
+    pub search_config: Option<String>,
+    pub per_page: Option<i64>,
+    pub max_per_page: Option<i64>,
+    pub default_order: Option<String>
+}
+
+// Extract container attributes
+pub fn extract_container(ast: &mut DeriveInput) -> deluxe::Result<ContainerAttrs> {
+    extract_attributes(ast)
+}
+
+// Extract attributes in field declaration order
+pub fn extract_attrs(ast: &mut DeriveInput) -> deluxe::Result<Vec<(Ident, Attrs)>> {
+    let mut attrs = vec![];
     if let Data::Struct(s) = &mut ast.data {
         for field in s.fields.iter_mut() {
-            if let Ok(attr) = extract_attributes(field) {
-                attrs.insert(field.ident.as_ref().unwrap().clone(), attr);
+            // Only fields without `#[psql]` are skipped, a malformed one is reported
+            if !field.attrs.iter().any(|a| a.path().is_ident("psql")) {
+                continue;
             }
+
+            // Fields handled by the `Encryption` derive count as encrypted
+            let is_encrypted = field.attrs.iter().any(|a| a.path().is_ident("encryption"));
+
+            let mut attr = extract_attributes::<_, Attrs>(field)?;
+            attr.encrypted |= is_encrypted;
+            attrs.push((field.ident.as_ref().unwrap().clone(), attr));
         }
     }
 
@@ -33,20 +75,66 @@ pub struct PropsAttrs{
     pub types: Type
 }
 
-// Extract props
-pub fn extract_props(ast: &mut DeriveInput) -> deluxe::Result<HashMap<Ident, PropsAttrs>> {
-    let mut attrs = HashMap::new();
+// PostgreSQL reserved key words that can't be used as bare identifiers
+const RESERVED: &[&str] = &[
+    "all", "analyse", "analyze", "and", "any", "array", "as", "asc", "asymmetric",
+    "authorization", "binary", "both", "case", "cast", "check", "collate", "collation",
+    "column", "concurrently", "constraint", "create", "cross", "current_catalog",
+    "current_date", "current_role", "current_schema", "current_time", "current_timestamp",
+    "current_user", "default", "deferrable", "desc", "distinct", "do", "else", "end",
+    "except", "false", "fetch", "for", "foreign", "freeze", "from", "full", "grant",
+    "group", "having", "ilike", "in", "initially", "inner", "intersect", "into", "is",
+    "isnull", "join", "lateral", "leading", "left", "like", "limit", "localtime",
+    "localtimestamp", "natural", "not", "notnull", "null", "offset", "on", "only", "or",
+    "order", "outer", "overlaps", "placing", "primary", "references", "returning", "right",
+    "select", "session_user", "similar", "some", "symmetric", "system_user", "table",
+    "tablesample", "then", "to", "trailing", "true", "union", "unique", "user", "using",
+    "variadic", "verbose", "when", "where", "window", "with"
+];
+
+// Quote identifiers that are reserved words or not plain lowercase
+pub fn quote_ident<T>(input: T) -> String
+    where T: ToString
+{
+    let input = input.to_string();
+
+    let re = Regex::new(r"^[a-z_][a-z0-9_$]*$").unwrap();
+    if re.is_match(&input) && !RESERVED.contains(&input.as_str()) {
+        return input;
+    }
+
+    format!("\"{}\"", input.replace('"', "\"\""))
+}
+
+// Extract props in field declaration order
+pub fn extract_props(ast: &mut DeriveInput) -> deluxe::Result<Vec<(Ident, PropsAttrs)>> {
+    let mut attrs = vec![];
     if let Data::Struct(s) = &mut ast.data {
         for field in s.fields.iter_mut() {
-            if let Ok(attr) = extract_attributes(field) {
-                attrs.insert(field.ident.as_ref().unwrap().clone(), attr);
+            if !field.attrs.iter().any(|a| a.path().is_ident("props")) {
+                continue;
             }
+
+            attrs.push((field.ident.as_ref().unwrap().clone(), extract_attributes(field)?));
         }
     }
 
     Ok(attrs)
 }
 
+// Namespace module holding the generated constants and parsers of a struct
+pub fn module_name(node: &Ident) -> Ident {
+    let name = stringcase::snake_case(&node.to_string());
+
+    // Keywords become raw identifiers (`r#type`), path keywords cannot be raw and get a trailing underscore
+    match name.as_str() {
+        "crate" | "self" | "super" => quote::format_ident!("{}_", name),
+        "gen" => Ident::new_raw(&name, node.span()),
+        _ if parse_str::<Ident>(&name).is_err() => Ident::new_raw(&name, node.span()),
+        _ => quote::format_ident!("{}", name)
+    }
+}
+
 // Create a syn::Type to String conversion
 pub fn type_to_string(input: &Type) -> String {
     format!("{}", quote::quote! { #input })
@@ -0,0 +1,69 @@
+use proc_macro2::TokenStream;
+use syn::{Ident, Type};
+
+use crate::extractors;
+
+pub fn stream(
+    node: &Ident,
+    table: &str,
+    fields: &[Ident],
+    columns: &[String],
+    types: &[Type]
+) -> TokenStream {
+    let module = extractors::module_name(node);
+    quote::quote! {
+        impl #node {
+            pub fn insert_query(&self) -> String {
+                let mut columns: Vec<&'static str> = vec![];
+                let mut values = vec![];
+
+                #(
+                    if !self.#fields.is_undefined() {
+                        columns.push(#columns);
+                        values.push(format!("${}", columns.len()));
+                    }
+                )*
+
+                if columns.is_empty() {
+                    return format!("INSERT INTO {} DEFAULT VALUES RETURNING {}", #table, #module::alias::ALL);
+                }
+
+                format!(
+                    "INSERT INTO {} ({}) VALUES ({}) RETURNING {}",
+                    #table,
+                    columns.join(", "),
+                    values.join(", "),
+                    #module::alias::ALL
+                )
+            }
+
+            pub fn bind_insert<'q>(
+                &self,
+                query: sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>
+            ) -> sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments> {
+                let mut query = query;
+
+                #(
+                    match self.#fields.clone() {
+                        nulls::Null::Undefined => {},
+                        nulls::Null::Null => query = query.bind(None::<#types>),
+                        nulls::Null::Value(value) => query = query.bind(value),
+                    }
+                )*
+
+                query
+            }
+
+            pub async fn insert<'e, E>(&self, executor: E) -> errors::Result<Self>
+                where E: sqlx::PgExecutor<'e>
+            {
+                let query = self.insert_query();
+                let row = self.bind_insert(sqlx::query(&query))
+                    .fetch_one(executor)
+                    .await;
+
+                #module::parsers::result(row)
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,66 @@
+use proc_macro2::TokenStream;
+use syn::{Ident, Type};
+
+use crate::extractors;
+
+pub fn stream(
+    node: &Ident,
+    table: &str,
+    aliased: &str,
+    key: &str,
+    key_type: &Type
+) -> TokenStream {
+    let module = extractors::module_name(node);
+    let select_by_key = format!("SELECT {} FROM {} WHERE {} = $1", aliased, table, key);
+    let delete_by_key = format!("DELETE FROM {} WHERE {} = $1", table, key);
+    let exists_by_key = format!("SELECT EXISTS(SELECT 1 FROM {} WHERE {} = $1)", table, key);
+
+    quote::quote! {
+        impl #node {
+            pub fn select_by_key_query() -> &'static str {
+                #select_by_key
+            }
+
+            pub fn delete_by_key_query() -> &'static str {
+                #delete_by_key
+            }
+
+            pub fn exists_by_key_query() -> &'static str {
+                #exists_by_key
+            }
+
+            pub async fn select_by_key<'e, E>(executor: E, key: #key_type) -> errors::Result<Self>
+                where E: sqlx::PgExecutor<'e>
+            {
+                let row = sqlx::query(Self::select_by_key_query())
+                    .bind(key)
+                    .fetch_one(executor)
+                    .await;
+
+                #module::parsers::result(row)
+            }
+
+            pub async fn delete_by_key<'e, E>(executor: E, key: #key_type) -> errors::Result<u64>
+                where E: sqlx::PgExecutor<'e>
+            {
+                let result = sqlx::query(Self::delete_by_key_query())
+                    .bind(key)
+                    .execute(executor)
+                    .await
+                    .map_err(errors::query)?;
+
+                Ok(result.rows_affected())
+            }
+
+            pub async fn exists_by_key<'e, E>(executor: E, key: #key_type) -> errors::Result<bool>
+                where E: sqlx::PgExecutor<'e>
+            {
+                sqlx::query_scalar::<_, bool>(Self::exists_by_key_query())
+                    .bind(key)
+                    .fetch_one(executor)
+                    .await
+                    .map_err(errors::query)
+            }
+        }
+    }
+}
\ No newline at end of file
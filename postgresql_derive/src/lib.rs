@@ -1,14 +1,21 @@
+mod binds;
 mod extractors;
+mod insert;
+mod keys;
+mod page;
+mod update;
 
 use deluxe::Result;
 use proc_macro2::TokenStream;
 use quote::format_ident;
-use syn::{Data, DeriveInput};
+use syn::{Data, DeriveInput, Error};
 
 // Start of derive and field attribute derives
 #[proc_macro_derive(PostgreSQL, attributes(psql, props))]
 pub fn main(stream: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    derive(stream.into()).unwrap().into()
+    derive(stream.into())
+        .unwrap_or_else(|e| e.into_compile_error())
+        .into()
 }
 
 // Start of derive and token processing
@@ -16,7 +23,31 @@ fn derive(stream: TokenStream) -> Result<TokenStream> {
     // Parse token stream
     let mut ast: DeriveInput = syn::parse2(stream)?;
     let node = &ast.ident.clone();
-    let prefix = stringcase::snake_case(&node.clone().to_string()).to_lowercase();
+    let vis = &ast.vis.clone();
+    let module = extractors::module_name(node);
+    let container = extractors::extract_container(&mut ast)?;
+
+    // Alias prefix stays distinct from the real table name so self-joins still work
+    let prefix = match container.alias.clone() {
+        Some(alias) => alias,
+        None => stringcase::snake_case(&node.clone().to_string()).to_lowercase()
+    };
+
+    let table = match container.table.clone() {
+        Some(table) => extractors::quote_ident(table),
+        None => extractors::quote_ident(&prefix)
+    };
+
+    let table = match container.schema.clone() {
+        Some(schema) => format!("{}.{}", extractors::quote_ident(schema), table),
+        None => table
+    };
+
+    let alias = extractors::quote_ident(&prefix);
+    let from = match table == alias {
+        true => table.clone(),
+        false => format!("{} AS {}", table, alias)
+    };
 
     let paginated = format_ident!("{}Page", node);
 
@@ -37,23 +68,116 @@ fn derive(stream: TokenStream) -> Result<TokenStream> {
     let mut tabled_values = vec![];
 
     let mut jsons = vec![];
+    let mut try_parsers = vec![];
+    let mut filterables = vec![];
+    let mut sortables = vec![];
+    let mut searchables = vec![];
+    let mut raw_columns = vec![];
+    let mut metas = vec![];
+    let mut not_nulls = vec![];
+
+    let attrs = extractors::extract_attrs(&mut ast)?;
+
+    // Resolve primary key, falling back to an `id` field
+    let keys = attrs.iter().filter(|(_, attr)| attr.primary_key).map(|(field, _)| field).collect::<Vec<_>>();
+    if keys.len() > 1 {
+        return Err(Error::new_spanned(keys[1], "only one #[psql(primary_key)] field is allowed"));
+    }
+
+    let key_index = match keys.first() {
+        Some(key) => attrs.iter().position(|(f, _)| f == *key),
+        None => attrs.iter().position(|(f, _)| f == "id")
+    };
+
+    for (index, (field, attr)) in attrs.into_iter().enumerate() {
+        let is_key = key_index == Some(index);
+        let raw_column = match attr.column.clone() {
+            Some(column) => column,
+            None => field.to_string().to_lowercase()
+        };
+
+        let column = extractors::quote_ident(&raw_column);
 
-    for (field, attr) in extractors::extract_attrs(&mut ast)? {
         let plain_field = format_ident!("{}", field.to_string().to_uppercase());
-        let aliased_value = format!("{}.{} AS {}_{}", prefix.clone(), field, prefix.clone(), field);
-        let plain_value = field.to_string().to_lowercase();
         let renamed_value = format!("{}_{}", prefix.clone(), field);
-        let tabled_value = format!("{}.{}", prefix.clone(), field);
+        let aliased_value = format!("{}.{} AS {}", alias, column, extractors::quote_ident(&renamed_value));
+        let plain_value = column.clone();
+        let tabled_value = format!("{}.{}", alias, column);
 
-        all_fields.push(plain_field);
+        all_fields.push(plain_field.clone());
         aliased_values.push(aliased_value);
         plain_values.push(plain_value);
         renamed_values.push(renamed_value.clone());
-        tabled_values.push(tabled_value);
+        tabled_values.push(tabled_value.clone());
 
         fields.push(field.clone());
         types.push(attr.types.clone());
-        names.push(renamed_value);
+        names.push(renamed_value.clone());
+
+        if is_key || attr.required {
+            not_nulls.push(plain_field.clone());
+        }
+
+        raw_columns.push(raw_column.clone());
+
+        let field_name = field.to_string();
+        let type_name = extractors::type_to_string(&attr.types);
+        let nullable = !(is_key || attr.required);
+        let (key, searchable, encrypted) = (is_key, attr.searchable, attr.encrypted);
+
+        metas.push(quote::quote! {
+            derives::ColumnMeta {
+                field: #field_name,
+                column: #raw_column,
+                alias: #renamed_value,
+                types: #type_name,
+                nullable: #nullable,
+                key: #key,
+                searchable: #searchable,
+                encrypted: #encrypted,
+            }
+        });
+
+        // API names accepted by Page filters and orders
+        let mut api_names = vec![stringcase::camel_case(&field.to_string()), field.to_string()];
+        api_names.push(tabled_value.clone());
+        api_names.dedup();
+
+        if attr.searchable {
+            match extractors::type_to_string(&attr.types).as_str() {
+                "String" => searchables.push(tabled_value.clone()),
+                _ => searchables.push(format!("{}::text", tabled_value))
+            }
+        }
+
+        filterables.push((api_names.clone(), plain_field.clone(), field.clone(), attr.filterable));
+        sortables.push((api_names, plain_field.clone(), field.clone(), attr.sortable));
+
+        // Strictness: required fields reject NULL, optional fields tolerate missing columns
+        let types = &attr.types;
+        let on_null = match attr.required {
+            true => quote::quote! { return Err(derives::ParseError::unexpected_null(column)) },
+            false => quote::quote! { nulls::Null::Null }
+        };
+
+        let on_missing = match attr.optional {
+            true => quote::quote! { nulls::Null::Undefined },
+            false => quote::quote! { return Err(derives::ParseError::missing(column)) }
+        };
+
+        try_parsers.push(quote::quote! {
+            let column = match plain {
+                true => #raw_column,
+                false => #renamed_value
+            };
+
+            data.#field = match row.try_get::<Option<#types>, &str>(column) {
+                Ok(Some(value)) => nulls::Null::Value(value),
+                Ok(None) => #on_null,
+                Err(sqlx::Error::ColumnNotFound(_)) => #on_missing,
+                Err(e) => return Err(derives::ParseError::decode(column, e)),
+            };
+        });
 
         let t = match attr.props.clone() {
             Some(t) => t,
@@ -106,18 +230,33 @@ fn derive(stream: TokenStream) -> Result<TokenStream> {
             }
         });
 
-        if field.to_string().as_str() == "id" {
+        let is_key = key_index.is_some_and(|index| fields[index] == field);
+        if is_key && extractors::type_to_string(&attr) == "String" {
             let field_name = format_ident!("set_insert_{}", field);
 
             setters.push(quote::quote!{
                 pub fn #field_name<T: ToString>(&self, value: T) -> Self {
                     let mut data = self.clone();
-                    let id = data.id().unwrap_or_default();
+                    let key = data.#field().unwrap_or_default();
 
-                    if id.is_empty() {
+                    if key.is_empty() {
                         data.#field = nulls::new(value.to_string());
                     }
 
+                    data
+                }
+            });
+        } else if is_key {
+            let field_name = format_ident!("set_insert_{}", field);
+
+            setters.push(quote::quote!{
+                pub fn #field_name<T: Into<#attr>>(&self, value: T) -> Self {
+                    let mut data = self.clone();
+
+                    if data.#field().is_none() {
+                        data.#field = nulls::new(value.into());
+                    }
+
                     data
                 }
             });
@@ -183,7 +322,7 @@ fn derive(stream: TokenStream) -> Result<TokenStream> {
 
                 if let Ok(rows) = rows {
                     for row in rows {
-                        list.push(parsers::parse(&row));
+                        list.push(#module::parsers::parse(&row));
                     }
                 }
 
@@ -202,204 +341,6 @@ fn derive(stream: TokenStream) -> Result<TokenStream> {
             }
         }
 
-        #[derive(Default, Debug, Clone, PartialEq)]
-        #[derive(Deserialize, Serialize)]
-        #[serde(rename_all = "camelCase")]
-        pub struct #paginated {
-            #[serde(skip_serializing_if = "Option::is_none")]
-            pub page: Option<i64>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            pub per_page: Option<i64>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            pub filtered_count: Option<i64>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            pub total_count: Option<i64>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            pub search: Option<String>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            pub filters: Option<String>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            pub orders: Option<String>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            pub records: Option<#node_list>,
-        }
-
-        impl actix_web::Responder for #paginated {
-            type Body = actix_web::body::BoxBody;
-
-            fn respond_to(self, _req: &actix_web::HttpRequest) -> actix_web::HttpResponse {
-                actix_web::HttpResponse::Ok().json(serde_json::json!({
-                    "code": 200,
-                    "data": self
-                }))
-            }
-        }
-
-        impl #paginated {
-            pub fn request(&self) -> Self {
-                let mut data = self.clone();
-                data.filtered_count = None;
-                data.total_count = None;
-
-                if data.page.clone().unwrap_or(0) < 1 {
-                    data.page = Some(1);
-                }
-
-                if data.per_page.clone().unwrap_or(0) < 1 {
-                    data.per_page = Some(10);
-                }
-
-                data
-            }
-
-            pub fn search(&self) -> String {
-                self.search.clone().unwrap_or_default()
-            }
-
-            pub fn filters(&self) -> (Vec<String>, Vec<serde_json::Value>, usize) {
-                let data = self.clone();
-                let mut filter = vec![];
-
-                if let Some(f) = data.filters.clone() {
-                    if let Ok(f) = serde_json::from_str::<Vec<crate::Filter>>(&f) {
-                        filter = f;
-                    }
-                }
-
-                let mut conds = vec![];
-                let mut vals =  vec![];
-                let mut idx = 0;
-
-                for (index, value) in filter.clone().into_iter().enumerate() {
-                    idx = index + 1;
-                    let mut col = value.cols.clone().unwrap_or_default();
-                    let op = value.ops.clone().unwrap_or_default();
-                    let val = value.vals.clone().unwrap_or(serde_json::Value::Null);
-
-                    if let Ok(re) = regex::Regex::new(r"[^a-zA-Z0-9._]") {
-                        col = re.replace_all(&col, "").to_string();
-                    }
-
-                    match op {
-                        crate::FilterOps::Gt => {
-                            conds.push(format!("{} > ${}", col, idx));
-                            vals.push(value.vals.unwrap_or(serde_json::Value::Null));
-                        },
-                        crate::FilterOps::Lt => {
-                            conds.push(format!("{} < ${}", col, idx));
-                            vals.push(value.vals.unwrap_or(serde_json::Value::Null));
-                        },
-                        crate::FilterOps::Like => {
-                            let v = match val.clone() {
-                                serde_json::Value::Null => "%NULL%".to_string(),
-                                serde_json::Value::Bool(d) => format!("%{}%", d),
-                                serde_json::Value::Number(d) => format!("%{}%", d),
-                                serde_json::Value::String(d) => format!("%{}%", d),
-                                serde_json::Value::Array(d) => format!("%{}%", serde_json::json!(d).to_string()),
-                                serde_json::Value::Object(d) => format!("%{}%", serde_json::json!(d).to_string()),
-                            };
-
-                            conds.push(format!("{} LIKE ${}", col, idx));
-                            vals.push(serde_json::Value::String(v));
-                        },
-                        crate::FilterOps::LikeLeft => {
-                            let v = match val.clone() {
-                                serde_json::Value::Null => "%NULL".to_string(),
-                                serde_json::Value::Bool(d) => format!("%{}", d),
-                                serde_json::Value::Number(d) => format!("%{}", d),
-                                serde_json::Value::String(d) => format!("%{}", d),
-                                serde_json::Value::Array(d) => format!("%{}", serde_json::json!(d).to_string()),
-                                serde_json::Value::Object(d) => format!("%{}", serde_json::json!(d).to_string()),
-                            };
-
-                            conds.push(format!("{} LIKE ${}", col, idx));
-                            vals.push(serde_json::Value::String(v));
-                        },
-                        crate::FilterOps::LikeRight => {
-                            let v = match val.clone() {
-                                serde_json::Value::Null => "NULL%".to_string(),
-                                serde_json::Value::Bool(d) => format!("{}%", d),
-                                serde_json::Value::Number(d) => format!("{}%", d),
-                                serde_json::Value::String(d) => format!("{}%", d),
-                                serde_json::Value::Array(d) => format!("{}%", serde_json::json!(d).to_string()),
-                                serde_json::Value::Object(d) => format!("{}%", serde_json::json!(d).to_string()),
-                            };
-
-                            conds.push(format!("{} LIKE ${}", col, idx));
-                            vals.push(serde_json::Value::String(v));
-                        },
-                        crate::FilterOps::Eq => {
-                            conds.push(format!("{} = ${}", col, idx));
-                            vals.push(value.vals.unwrap_or(serde_json::Value::Null));
-                        },
-                        _ => {}
-                    };
-
-                }
-
-                (conds, vals, idx)
-            }
-
-            pub fn limit(&self) -> (i64, i64, i64) {
-                let mut page = self.page.unwrap_or(1);
-                if page < 1 {
-                    page = 1;
-                }
-
-                let mut per_page = self.per_page.unwrap_or(5);
-                if per_page < 1 {
-                    per_page = 5;
-                }
-
-                let max_page = (self.filtered_count.unwrap_or(0) + per_page - 1) / per_page;
-                if page > max_page {
-                    page = max_page;
-                }
-
-                let offset = (page - 1) * per_page;
-
-                (page as i64, per_page as i64, offset as i64)
-            }
-
-            pub fn orders<T: ToString>(&self, default_order:T) -> String {
-                let data = self.clone();
-                let order = default_order.to_string();
-                let mut ovec = vec![];
-
-                if let Some(o) = data.orders {
-                    if let Ok(o) = serde_json::from_str::<Vec<crate::Order>>(&o) {
-                        for item in o {
-                            let mut col = item.cols.clone().unwrap_or_default();
-                            if let Ok(re) = regex::Regex::new(r"[^a-zA-Z0-9._]") {
-                                col = re.replace_all(&col, "").to_string();
-                            }
-
-                            match item.ops.clone().unwrap_or_default() {
-                                crate::OrderOps::Asc => ovec.push(format!("{} ASC", col)),
-                                crate::OrderOps::Desc => ovec.push(format!("{} DESC", col)),
-                            }
-                        }
-                    }
-                }
-
-                if ovec.is_empty() {
-                    format!("ORDER BY {}", order)
-                } else {
-                    format!("ORDER BY {}", ovec.join(", "))
-                }
-            }
-
-            pub fn response(&self) -> Self {
-                let mut data = self.clone();
-
-                data.search = None;
-                data.filters = None;
-                data.orders = None;
-
-                data
-            }
-        }
-
         impl #node {
             pub fn is_empty(&self) -> bool {
                 *self == Self::default()
@@ -425,6 +366,24 @@ fn derive(stream: TokenStream) -> Result<TokenStream> {
                 data
             }
 
+            pub fn try_parse(row: &sqlx::postgres::PgRow) -> Result<Self, derives::ParseError> {
+                Self::try_parse_layout(row, false)
+            }
+
+            pub fn try_parse_plain(row: &sqlx::postgres::PgRow) -> Result<Self, derives::ParseError> {
+                Self::try_parse_layout(row, true)
+            }
+
+            fn try_parse_layout(row: &sqlx::postgres::PgRow, plain: bool) -> Result<Self, derives::ParseError> {
+                use sqlx::Row;
+
+                let mut data = Self::default();
+
+                #(#try_parsers)*
+
+                Ok(data)
+            }
+
             pub fn as_response(&self) -> actix_web::Result<actix_web::HttpResponse> {
                 Ok(actix_web::HttpResponse::Ok().json(serde_json::json!({
                     "code": 200,
@@ -487,83 +446,223 @@ fn derive(stream: TokenStream) -> Result<TokenStream> {
         }
     });
 
-    // Set parsers
-    token.extend(quote::quote! {
-        pub mod parsers {
-            use nulls::Null;
+    // Set paginated request and response, only marked columns are allowed once any is marked
+    if filterables.iter().any(|(.., marked)| *marked) {
+        filterables.retain(|(.., marked)| *marked);
+    }
 
-            use crate::#node;
+    if sortables.iter().any(|(.., marked)| *marked) {
+        sortables.retain(|(.., marked)| *marked);
+    }
 
-            pub fn parse(row: &sqlx::postgres::PgRow) -> #node {
-                #node::parse(row)
-            }
+    let search = match container.search.clone().unwrap_or(String::from("ilike")).as_str() {
+        "ilike" => {
+            let conds = searchables.iter().map(|col| format!("{} ILIKE {{0}}", col)).collect::<Vec<_>>();
+            format!("({})", conds.join(" OR "))
+        },
+        "fulltext" => format!(
+            "to_tsvector('{1}', concat_ws(' ', {0})) @@ websearch_to_tsquery('{1}', {{0}})",
+            searchables.join(", "),
+            container.search_config.clone().unwrap_or(String::from("simple")).replace('\'', "")
+        ),
+        _ => return Err(Error::new_spanned(node, "search must be either \"ilike\" or \"fulltext\""))
+    };
+
+    let is_fulltext = container.search.as_deref() == Some("fulltext");
+    let search = match searchables.is_empty() {
+        true => None,
+        false => Some((search, is_fulltext))
+    };
+
+    // Default order is written as `created_at desc nulls last, name`
+    let mut default_order = vec![];
+    for key in container.default_order.clone().unwrap_or_default().split(',').filter(|key| !key.trim().is_empty()) {
+        let words = key.split_whitespace().collect::<Vec<_>>();
+        let index = fields
+            .iter()
+            .zip(raw_columns.iter())
+            .position(|(field, column)| *field == words[0] || *column == words[0])
+            .ok_or_else(|| Error::new_spanned(node, format!("default_order has unknown column `{}`", words[0])))?;
+
+        let direction = words[1..].iter().map(|word| word.to_lowercase()).collect::<Vec<_>>();
+        let (desc, nulls_first) = match direction.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+            [] | ["asc"] => (false, false),
+            ["desc"] => (true, true),
+            ["nulls", "first"] | ["asc", "nulls", "first"] => (false, true),
+            ["nulls", "last"] | ["asc", "nulls", "last"] => (false, false),
+            ["desc", "nulls", "first"] => (true, true),
+            ["desc", "nulls", "last"] => (true, false),
+            _ => return Err(Error::new_spanned(node, format!("default_order has invalid key `{}`", key.trim())))
+        };
 
-            pub fn result(row: sqlx::Result<sqlx::postgres::PgRow>) -> errors::Result<#node> {
-                let result = row.map_err(errors::query)?;
-                let row = parse(&result);
+        default_order.push((all_fields[index].clone(), desc, nulls_first));
+    }
 
-                match !row.is_empty() {
-                    true => Ok(row),
-                    false => Err(errors::str_to("table row not found"))
-                }
-            }
+    let tiebreaker = key_index.map(|index| all_fields[index].clone());
 
-            pub fn relational(row: &sqlx::postgres::PgRow) -> Null<#node> {
-                let row = parse(row);
+    let per_page = container.per_page.unwrap_or(10);
+    let max_per_page = container.max_per_page.unwrap_or(per_page.max(100));
+    if per_page < 1 || max_per_page < per_page {
+        return Err(Error::new_spanned(node, "per_page must be positive and not greater than max_per_page"));
+    }
+
+    token.extend(binds::stream(node, &all_fields, &fields, &types));
+    token.extend(page::stream(page::PageSpec {
+        node,
+        paginated: &paginated,
+        node_list: &node_list,
+        filterables: &filterables,
+        sortables: &sortables,
+        search: &search,
+        columns: &all_fields,
+        fields: &fields,
+        not_nulls: &not_nulls,
+        default_order: &default_order,
+        tiebreaker: &tiebreaker,
+        per_page,
+        max_per_page
+    }));
+
+    // Set sqlx::FromRow for either column layout
+    let layout = match container.from_row.clone().unwrap_or(String::from("auto")).as_str() {
+        "auto" => Some(quote::quote! {
+            {
+                use sqlx::Row;
 
-                match row.is_empty() {
-                    true => Null::Undefined,
-                    false => Null::Value(row)
+                !row.columns().iter().any(|c| [#(#names),*].contains(&sqlx::Column::name(c)))
+            }
+        }),
+        "renamed" => Some(quote::quote! { false }),
+        "plain" => Some(quote::quote! { true }),
+        "none" => None,
+        _ => return Err(Error::new_spanned(node, "from_row must be one of \"auto\", \"renamed\", \"plain\" or \"none\""))
+    };
+
+    if let Some(plain) = layout {
+        token.extend(quote::quote! {
+            impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for #node {
+                fn from_row(row: &'r sqlx::postgres::PgRow) -> sqlx::Result<Self> {
+                    let plain = #plain;
+
+                    Self::try_parse_layout(row, plain).map_err(|e| match e.kind {
+                        derives::ParseErrorKind::Missing => sqlx::Error::ColumnNotFound(e.column),
+                        _ => sqlx::Error::ColumnDecode {
+                            index: e.column.clone(),
+                            source: Box::new(e)
+                        }
+                    })
                 }
             }
+        });
+    }
+
+    // Set table names
+    token.extend(quote::quote! {
+        impl #node {
+            pub const TABLE: &'static str = #table;
+            pub const ALIAS: &'static str = #alias;
+            pub const FROM: &'static str = #from;
+            pub const COLUMNS: &'static [derives::ColumnMeta] = &[#( #metas ),*];
         }
     });
 
-    // Extend token alias
-    token.extend(quote::quote!{
-        pub mod alias {
-            pub const ALL: &'static str = #all_aliased_values;
-
-            #(
-                pub const #all_fields: &'static str = #aliased_values;
-            )*
+    // Set insert builder
+    token.extend(insert::stream(
+        node, &from, &fields,
+        &plain_values, &types
+    ));
+
+    // Set update builder and key helpers
+    if let Some(index) = key_index {
+        let key = &tabled_values[index];
+        let key_type = &types[index];
+
+        // A struct holding only its key has nothing to update
+        if fields.len() > 1 {
+            token.extend(update::stream(
+                node, &from, (&fields[index], key, key_type),
+                &fields, &plain_values, &types
+            ));
         }
-    });
 
-    // Extend token plain
-    token.extend(quote::quote!{
-        pub mod plain {
-            pub const ALL: &'static str = #all_plain_values;
+        token.extend(keys::stream(
+            node, &from, &all_aliased_values,
+            key, key_type
+        ));
+    }
 
-            #(
-                pub const #all_fields: &'static str = #plain_values;
-            )*
-        }
-    });
+    // Generated constants and parsers live under a module named after the struct
+    token.extend(quote::quote! {
+        #vis mod #module {
+            pub mod parsers {
+                use nulls::Null;
 
-    // Extend token renamed
-    token.extend(quote::quote!{
-        pub mod renamed {
-            pub const ALL: &'static str = #all_renamed_values;
+                use super::super::#node;
 
-            #(
-                pub const #all_fields: &'static str = #renamed_values;
-            )*
-        }
-    });
+                pub fn parse(row: &sqlx::postgres::PgRow) -> #node {
+                    #node::parse(row)
+                }
 
-    // Extend token table
-    token.extend(quote::quote!{
-        pub mod tables {
-            pub const ALL: &'static str = #all_tabled_values;
+                pub fn result(row: sqlx::Result<sqlx::postgres::PgRow>) -> errors::Result<#node> {
+                    let result = row.map_err(errors::query)?;
+                    let row = parse(&result);
 
-            #(
-                pub const #all_fields: &'static str = #tabled_values;
-            )*
+                    match !row.is_empty() {
+                        true => Ok(row),
+                        false => Err(errors::str_to("table row not found"))
+                    }
+                }
+
+                pub fn try_result(row: sqlx::Result<sqlx::postgres::PgRow>) -> errors::Result<#node> {
+                    let result = row.map_err(errors::query)?;
+
+                    #node::try_parse(&result).map_err(|e| errors::str_to(&e.to_string()))
+                }
+
+                pub fn relational(row: &sqlx::postgres::PgRow) -> Null<#node> {
+                    let row = parse(row);
+
+                    match row.is_empty() {
+                        true => Null::Undefined,
+                        false => Null::Value(row)
+                    }
+                }
+            }
+
+            pub mod alias {
+                pub const ALL: &'static str = #all_aliased_values;
+
+                #(
+                    pub const #all_fields: &'static str = #aliased_values;
+                )*
+            }
+
+            pub mod plain {
+                pub const ALL: &'static str = #all_plain_values;
+
+                #(
+                    pub const #all_fields: &'static str = #plain_values;
+                )*
+            }
+
+            pub mod renamed {
+                pub const ALL: &'static str = #all_renamed_values;
+
+                #(
+                    pub const #all_fields: &'static str = #renamed_values;
+                )*
+            }
+
+            pub mod tables {
+                pub const ALL: &'static str = #all_tabled_values;
+
+                #(
+                    pub const #all_fields: &'static str = #tabled_values;
+                )*
+            }
         }
     });
 
-
     // Return the new token
     Ok(token)
 }
\ No newline at end of file
@@ -0,0 +1,747 @@
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use syn::Ident;
+
+use crate::extractors;
+
+// Inputs of the generated Page type, grouped from the struct fields and container attributes
+pub struct PageSpec<'a> {
+    pub node: &'a Ident,
+    pub paginated: &'a Ident,
+    pub node_list: &'a Ident,
+    pub filterables: &'a [(Vec<String>, Ident, Ident, bool)],
+    pub sortables: &'a [(Vec<String>, Ident, Ident, bool)],
+    pub search: &'a Option<(String, bool)>,
+    pub columns: &'a [Ident],
+    pub fields: &'a [Ident],
+    pub not_nulls: &'a [Ident],
+    pub default_order: &'a [(Ident, bool, bool)],
+    pub tiebreaker: &'a Option<Ident>,
+    pub per_page: i64,
+    pub max_per_page: i64
+}
+
+pub fn stream(spec: PageSpec) -> TokenStream {
+    let PageSpec {
+        node, paginated, node_list,
+        filterables, sortables, search,
+        columns, fields, not_nulls,
+        default_order, tiebreaker,
+        per_page: default_per_page, max_per_page
+    } = spec;
+
+    let module = extractors::module_name(node);
+
+    let default_columns = default_order.iter().map(|(column, ..)| column.clone()).collect::<Vec<_>>();
+    let default_descs = default_order.iter().map(|(_, desc, _)| *desc).collect::<Vec<_>>();
+    let default_nulls = default_order.iter().map(|(.., nulls_first)| *nulls_first).collect::<Vec<_>>();
+
+    // Primary key is always the last sort key so equal rows keep a stable order
+    let tiebreaker = match tiebreaker {
+        Some(key) => quote::quote! {
+            if !keys.iter().any(|(col, ..)| *col == #module::tables::#key) {
+                keys.push((#module::tables::#key, false, false));
+            }
+        },
+        None => quote::quote! {}
+    };
+
+    let bind = format_ident!("{}Bind", node);
+    let segment = format_ident!("{}Segment", node);
+    let conditions = format_ident!("{}Where", node);
+
+    let filter_names = filterables.iter().map(|(names, ..)| names.clone()).collect::<Vec<_>>();
+    let filter_columns = filterables.iter().map(|(_, column, ..)| column.clone()).collect::<Vec<_>>();
+    let filter_expected = filterables.iter().map(|(names, ..)| names[0].clone()).collect::<Vec<_>>().join(", ");
+
+    let sort_names = sortables.iter().map(|(names, ..)| names.clone()).collect::<Vec<_>>();
+    let sort_columns = sortables.iter().map(|(_, column, ..)| column.clone()).collect::<Vec<_>>();
+    let sort_expected = sortables.iter().map(|(names, ..)| names[0].clone()).collect::<Vec<_>>().join(", ");
+
+    // Search template uses `{0}` as its placeholder, every occurrence binds the term again
+    let search_clause = match search {
+        Some((template, is_fulltext)) => {
+            let value = match is_fulltext {
+                true => quote::quote! { search.clone() },
+                false => quote::quote! { format!("%{}%", search) }
+            };
+
+            quote::quote! {
+                let search = self.search().trim().to_string();
+                if search.is_empty() {
+                    return None;
+                }
+
+                let mut condition = vec![];
+                for (index, part) in #template.split("{0}").enumerate() {
+                    if index > 0 {
+                        condition.push(#segment::Bind(#bind::__Pattern(#value)));
+                    }
+
+                    condition.push(#segment::Sql(part.to_string()));
+                }
+
+                Some(condition)
+            }
+        },
+        None => quote::quote! {
+            None
+        }
+    };
+
+    quote::quote! {
+        #[derive(Default, Debug, Clone, PartialEq)]
+        #[derive(Deserialize, Serialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct #paginated {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub page: Option<i64>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub per_page: Option<i64>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub filtered_count: Option<i64>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub total_count: Option<i64>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub total_pages: Option<i64>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub has_next: Option<bool>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub has_prev: Option<bool>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub search: Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub filters: Option<derives::FilterNode>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub orders: Option<Vec<derives::Order>>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub after: Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub before: Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub next_cursor: Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub prev_cursor: Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub records: Option<#node_list>,
+        }
+
+        impl actix_web::Responder for #paginated {
+            type Body = actix_web::body::BoxBody;
+
+            fn respond_to(self, _req: &actix_web::HttpRequest) -> actix_web::HttpResponse {
+                actix_web::HttpResponse::Ok().json(serde_json::json!({
+                    "code": 200,
+                    "data": self
+                }))
+            }
+        }
+
+        // Reads a JSON body when one is sent, the query string otherwise
+        impl actix_web::FromRequest for #paginated {
+            type Error = actix_web::Error;
+            type Future = std::pin::Pin<Box<dyn std::future::Future<Output = Result<Self, Self::Error>>>>;
+
+            fn from_request(req: &actix_web::HttpRequest, payload: &mut actix_web::dev::Payload) -> Self::Future {
+                let query = req.query_string().to_string();
+                let is_json = req
+                    .headers()
+                    .get(actix_web::http::header::CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .map(|value| value.starts_with("application/json"))
+                    .unwrap_or(false);
+
+                let body = <actix_web::web::Bytes as actix_web::FromRequest>::from_request(req, payload);
+
+                Box::pin(async move {
+                    let bad_request = |message: String| -> actix_web::Error {
+                        let response = actix_web::HttpResponse::BadRequest().json(serde_json::json!({
+                            "code": 400,
+                            "message": message
+                        }));
+
+                        actix_web::error::InternalError::from_response(message, response).into()
+                    };
+
+                    let body = body.await?;
+                    if is_json && !body.is_empty() {
+                        return serde_json::from_slice::<Self>(&body)
+                            .map_err(|e| bad_request(format!("invalid page request: {}", e)));
+                    }
+
+                    let pairs = actix_web::web::Query::<Vec<(String, String)>>::from_query(&query)
+                        .map_err(|e| bad_request(format!("invalid query string: {}", e)))?;
+
+                    let params = derives::PageQuery::from_pairs(pairs.into_inner())
+                        .map_err(|e| bad_request(format!("invalid page request: {}", e)))?;
+
+                    Ok(Self {
+                        page: params.page,
+                        per_page: params.per_page,
+                        search: params.search,
+                        filters: params.filters,
+                        orders: params.orders,
+                        after: params.after,
+                        before: params.before,
+                        ..Default::default()
+                    })
+                })
+            }
+        }
+
+        impl #paginated {
+            pub const DEFAULT_PER_PAGE: i64 = #default_per_page;
+            pub const MAX_PER_PAGE: i64 = #max_per_page;
+            pub const MAX_FILTER_DEPTH: usize = 4;
+            pub const MAX_SORT_KEYS: usize = 4;
+
+            const NOT_NULL_COLUMNS: &'static [&'static str] = &[#( #module::tables::#not_nulls ),*];
+
+            pub fn request(&self) -> Self {
+                let mut data = self.clone();
+                data.filtered_count = None;
+                data.total_count = None;
+                data.total_pages = None;
+                data.has_next = None;
+                data.has_prev = None;
+                data.next_cursor = None;
+                data.prev_cursor = None;
+
+                data.page = Some(self.page.unwrap_or(1).max(1));
+                data.per_page = Some(self.per_page());
+
+                data
+            }
+
+            pub fn per_page(&self) -> i64 {
+                match self.per_page {
+                    Some(per_page) if per_page > 0 => per_page.min(Self::MAX_PER_PAGE),
+                    _ => Self::DEFAULT_PER_PAGE
+                }
+            }
+
+            pub fn total_pages(&self) -> Option<i64> {
+                let per_page = self.per_page();
+
+                // Rounded up without adding to the count, which may be as large as `i64::MAX`
+                self.filtered_count
+                    .map(|count| count.max(0))
+                    .map(|count| (count / per_page + i64::from(count % per_page > 0)).max(1))
+            }
+
+            pub fn filter_column(name: &str) -> errors::Result<&'static str> {
+                match name {
+                    #( #(#filter_names)|* => Ok(#module::tables::#filter_columns), )*
+                    _ => Err(errors::str_to(&format!(
+                        "unknown filter column `{}`, expected one of: {}",
+                        name, #filter_expected
+                    )))
+                }
+            }
+
+            pub fn sort_column(name: &str) -> errors::Result<&'static str> {
+                match name {
+                    #( #(#sort_names)|* => Ok(#module::tables::#sort_columns), )*
+                    _ => Err(errors::str_to(&format!(
+                        "unknown sort column `{}`, expected one of: {}",
+                        name, #sort_expected
+                    )))
+                }
+            }
+
+            pub fn search(&self) -> String {
+                self.search.clone().unwrap_or_default()
+            }
+
+            pub fn search_clause(&self) -> Option<Vec<#segment>> {
+                #search_clause
+            }
+
+            pub fn filters(&self) -> errors::Result<#conditions> {
+                let mut conds = #conditions::default();
+                let node = match self.filters.clone() {
+                    Some(node) => node,
+                    None => return Ok(conds)
+                };
+
+                if node.depth() > Self::MAX_FILTER_DEPTH {
+                    return Err(errors::str_to(&format!(
+                        "filters can't be nested deeper than {} groups",
+                        Self::MAX_FILTER_DEPTH
+                    )));
+                }
+
+                // Top level AND is kept flat, every member becomes its own condition
+                match node {
+                    derives::FilterNode::And(items) => for item in items {
+                        conds.and(Self::filter_group(item)?);
+                    },
+                    node => conds.and(Self::filter_group(node)?),
+                }
+
+                Ok(conds)
+            }
+
+            // Empty groups follow SQL: an empty AND matches every row, an empty OR none
+            fn filter_group(node: derives::FilterNode) -> errors::Result<Vec<#segment>> {
+                let sql = |d: &str| #segment::Sql(d.to_string());
+                let (items, joint, empty) = match node {
+                    derives::FilterNode::Filter(filter) => return Self::filter_condition(filter),
+                    derives::FilterNode::Not(item) => {
+                        let mut condition = vec![sql("NOT (")];
+                        condition.extend(Self::filter_group(*item)?);
+                        condition.push(sql(")"));
+
+                        return Ok(condition);
+                    },
+                    derives::FilterNode::And(items) => (items, " AND ", "TRUE"),
+                    derives::FilterNode::Or(items) => (items, " OR ", "FALSE"),
+                };
+
+                if items.is_empty() {
+                    return Ok(vec![sql(empty)]);
+                }
+
+                let mut condition = vec![];
+                for item in items {
+                    condition.push(sql(if condition.is_empty() { "(" } else { joint }));
+                    condition.extend(Self::filter_group(item)?);
+                }
+
+                condition.push(sql(")"));
+                Ok(condition)
+            }
+
+            fn filter_condition(filter: derives::Filter) -> errors::Result<Vec<#segment>> {
+                let sql = |d: String| #segment::Sql(d);
+                let col = Self::filter_column(&filter.cols.unwrap_or_default())?;
+                let op = filter.ops.unwrap_or_default();
+                let val = filter.vals.unwrap_or(serde_json::Value::Null);
+
+                // Binds are coerced into the column type declared by `#[psql(types = ...)]`
+                let typed = |val: serde_json::Value| #bind::coerce(col, val).map(#segment::Bind);
+                let pattern = |val: &serde_json::Value, left: &str, right: &str| #bind::pattern(col, val, left, right)
+                    .map(#segment::Bind);
+
+                let compare = |cmp: &str| -> errors::Result<Vec<#segment>> {
+                    Ok(vec![sql(format!("{} {} ", col, cmp)), typed(val.clone())?])
+                };
+
+                let condition = match op {
+                    derives::FilterOps::Eq if val.is_null() => vec![sql(format!("{} IS NULL", col))],
+                    derives::FilterOps::Neq if val.is_null() => vec![sql(format!("{} IS NOT NULL", col))],
+                    derives::FilterOps::Eq => compare("=")?,
+                    derives::FilterOps::Neq => compare("<>")?,
+                    derives::FilterOps::Gt => compare(">")?,
+                    derives::FilterOps::Gte => compare(">=")?,
+                    derives::FilterOps::Lt => compare("<")?,
+                    derives::FilterOps::Lte => compare("<=")?,
+                    derives::FilterOps::Like => vec![
+                        sql(format!("{} LIKE ", #bind::cast_text(col))),
+                        pattern(&val, "%", "%")?
+                    ],
+                    derives::FilterOps::LikeLeft => vec![
+                        sql(format!("{} LIKE ", #bind::cast_text(col))),
+                        pattern(&val, "%", "")?
+                    ],
+                    derives::FilterOps::LikeRight => vec![
+                        sql(format!("{} LIKE ", #bind::cast_text(col))),
+                        pattern(&val, "", "%")?
+                    ],
+                    derives::FilterOps::ILike => vec![
+                        sql(format!("{} ILIKE ", #bind::cast_text(col))),
+                        pattern(&val, "%", "%")?
+                    ],
+                    derives::FilterOps::StartsWith => match val {
+                        serde_json::Value::Array(items) => {
+                            let mut patterns = vec![];
+                            for item in items.iter() {
+                                if let #bind::__Pattern(d) = #bind::pattern(col, item, "", "%")? {
+                                    patterns.push(d);
+                                }
+                            }
+
+                            vec![
+                                sql(format!("{} LIKE ANY(", #bind::cast_text(col))),
+                                #segment::Bind(#bind::__Patterns(patterns)),
+                                sql(String::from(")"))
+                            ]
+                        },
+                        val => vec![
+                            sql(format!("{} LIKE ", #bind::cast_text(col))),
+                            pattern(&val, "", "%")?
+                        ]
+                    },
+                    derives::FilterOps::In | derives::FilterOps::NotIn => {
+                        let items = match val {
+                            serde_json::Value::Array(items) => items,
+                            _ => return Err(errors::str_to(&format!("filter on `{}` expects an array of values", col)))
+                        };
+
+                        let is_in = op == derives::FilterOps::In;
+                        match items.is_empty() {
+                            true => vec![sql(String::from(if is_in { "FALSE" } else { "TRUE" }))],
+                            false => {
+                                let mut condition = vec![sql(format!("{} {} (", col, if is_in { "IN" } else { "NOT IN" }))];
+                                for (index, item) in items.into_iter().enumerate() {
+                                    if index > 0 {
+                                        condition.push(sql(String::from(", ")));
+                                    }
+
+                                    condition.push(typed(item)?);
+                                }
+
+                                condition.push(sql(String::from(")")));
+                                condition
+                            }
+                        }
+                    },
+                    derives::FilterOps::Between => match val {
+                        serde_json::Value::Array(items) if items.len() == 2 => {
+                            let mut items = items.into_iter();
+                            let low = typed(items.next().unwrap_or_default())?;
+                            let high = typed(items.next().unwrap_or_default())?;
+
+                            vec![sql(format!("{} BETWEEN ", col)), low, sql(String::from(" AND ")), high]
+                        },
+                        _ => return Err(errors::str_to(&format!("filter on `{}` expects an array of two values", col)))
+                    },
+                    derives::FilterOps::IsNull => vec![sql(format!("{} IS NULL", col))],
+                    derives::FilterOps::IsNotNull => vec![sql(format!("{} IS NOT NULL", col))],
+                };
+
+                Ok(condition)
+            }
+
+            pub fn limit(&self) -> (i64, i64, i64) {
+                let mut page = self.page.unwrap_or(1).max(1);
+                let per_page = self.per_page();
+
+                // Keyset pagination never skips rows
+                if self.after.is_some() || self.before.is_some() {
+                    return (1, per_page, 0);
+                }
+
+                if let Some(total_pages) = self.total_pages() {
+                    page = page.min(total_pages);
+                }
+
+                (page, per_page, (page - 1).saturating_mul(per_page))
+            }
+
+            // Sort keys as `(column, desc, nulls_first)`, falling back to the default order
+            fn sort_keys(&self) -> errors::Result<Vec<(&'static str, bool, bool)>> {
+                let orders = self.orders.clone().unwrap_or_default();
+                if orders.len() > Self::MAX_SORT_KEYS {
+                    return Err(errors::str_to(&format!("at most {} orders are allowed", Self::MAX_SORT_KEYS)));
+                }
+
+                let mut keys: Vec<(&'static str, bool, bool)> = vec![];
+                for item in orders {
+                    let col = Self::sort_column(&item.cols.unwrap_or_default())?;
+                    let desc = item.ops.unwrap_or_default() == derives::OrderOps::Desc;
+                    let nulls_first = match item.nulls {
+                        Some(derives::OrderNulls::First) => true,
+                        Some(derives::OrderNulls::Last) => false,
+                        None => desc
+                    };
+
+                    if !keys.iter().any(|(c, ..)| *c == col) {
+                        keys.push((col, desc, nulls_first));
+                    }
+                }
+
+                if keys.is_empty() {
+                    keys = vec![#( (#module::tables::#default_columns, #default_descs, #default_nulls) ),*];
+                }
+
+                #tiebreaker
+
+                Ok(keys)
+            }
+
+            fn sort_value(record: &#node, column: &str) -> serde_json::Value {
+                match column {
+                    #( #module::tables::#columns => serde_json::to_value(&record.#fields).unwrap_or_default(), )*
+                    _ => serde_json::Value::Null
+                }
+            }
+
+            pub fn orders(&self) -> errors::Result<String> {
+                let mut ovec = vec![];
+
+                // Paging backwards reads rows in reverse, `with_cursors` restores the order
+                let backward = self.before.is_some();
+
+                for (col, desc, nulls_first) in self.sort_keys()? {
+                    let desc = desc != backward;
+                    let nulls_first = nulls_first != backward;
+                    let mut order = format!("{} {}", col, if desc { "DESC" } else { "ASC" });
+
+                    // NULLs come last for ASC and first for DESC unless asked otherwise
+                    if nulls_first != desc {
+                        order.push_str(if nulls_first { " NULLS FIRST" } else { " NULLS LAST" });
+                    }
+
+                    ovec.push(order);
+                }
+
+                match ovec.is_empty() {
+                    true => Ok(String::new()),
+                    false => Ok(format!("ORDER BY {}", ovec.join(", ")))
+                }
+            }
+
+            pub fn keyset(&self) -> errors::Result<#conditions> {
+                let mut conds = #conditions::default();
+                let (token, backward) = match (self.after.clone(), self.before.clone()) {
+                    (Some(_), Some(_)) => return Err(errors::str_to("only one of `after` or `before` can be set")),
+                    (Some(token), None) => (token, false),
+                    (None, Some(token)) => (token, true),
+                    (None, None) => return Ok(conds),
+                };
+
+                let keys = self.sort_keys()?;
+                if keys.is_empty() {
+                    return Err(errors::str_to("cursor pagination requires at least one order"));
+                }
+
+                let cursor = derives::Cursor::decode(&token)
+                    .filter(|cursor| cursor.values().len() == keys.len())
+                    .ok_or_else(|| errors::str_to("invalid cursor"))?;
+
+                // Cursor values are bound with the type of their sort column, NULLs are compared with IS NULL
+                let mut binds = vec![];
+                for ((col, ..), value) in keys.iter().zip(cursor.values().iter()) {
+                    binds.push(match value {
+                        serde_json::Value::Null => None,
+                        value => Some(#segment::Bind(
+                            #bind::coerce(col, value.clone()).map_err(|_| errors::str_to("invalid cursor"))?
+                        ))
+                    });
+                }
+
+                let sql = |d: String| #segment::Sql(d);
+                let mut condition = vec![];
+
+                // Row values only compare correctly for NOT NULL keys sharing one direction
+                let uniform = keys.iter().all(|(col, desc, _)| *desc == keys[0].1 && Self::NOT_NULL_COLUMNS.contains(col))
+                    && binds.iter().all(Option::is_some);
+
+                if uniform {
+                    let cols = keys.iter().map(|(col, ..)| col.to_string()).collect::<Vec<_>>().join(", ");
+                    let compare = if keys[0].1 != backward { "<" } else { ">" };
+                    condition.push(sql(format!("({}) {} (", cols, compare)));
+
+                    for (index, bind) in binds.into_iter().flatten().enumerate() {
+                        if index > 0 {
+                            condition.push(sql(String::from(", ")));
+                        }
+
+                        condition.push(bind);
+                    }
+
+                    condition.push(sql(String::from(")")));
+                    conds.and(condition);
+
+                    return Ok(conds);
+                }
+
+                for (i, (col, desc, nulls_first)) in keys.iter().enumerate() {
+                    let desc = *desc != backward;
+                    let nulls_first = *nulls_first != backward;
+                    let compare = if desc { "<" } else { ">" };
+                    let mut ands = vec![];
+
+                    for (j, (prev, ..)) in keys.iter().take(i).enumerate() {
+                        match binds[j].clone() {
+                            Some(bind) => ands.extend([sql(format!("{} = ", prev)), bind]),
+                            None => ands.push(sql(format!("{} IS NULL", prev))),
+                        }
+
+                        ands.push(sql(String::from(" AND ")));
+                    }
+
+                    // Rows past the cursor value, NULLs sort together at one end
+                    match (binds[i].clone(), nulls_first) {
+                        (None, true) => ands.push(sql(format!("{} IS NOT NULL", col))),
+                        (None, false) => continue,
+                        (Some(bind), _) if nulls_first || Self::NOT_NULL_COLUMNS.contains(col) => {
+                            ands.extend([sql(format!("{} {} ", col, compare)), bind])
+                        },
+                        (Some(bind), _) => ands.extend([
+                            sql(format!("({} {} ", col, compare)),
+                            bind,
+                            sql(format!(" OR {} IS NULL)", col))
+                        ]),
+                    }
+
+                    condition.push(sql(String::from(if condition.is_empty() { "((" } else { " OR (" })));
+                    condition.extend(ands);
+                    condition.push(sql(String::from(")")));
+                }
+
+                match condition.is_empty() {
+                    true => condition.push(sql(String::from("FALSE"))),
+                    false => condition.push(sql(String::from(")"))),
+                }
+
+                conds.and(condition);
+                Ok(conds)
+            }
+
+            // Records are fetched with one extra row, which only tells whether the walk goes on
+            pub fn with_cursors(&self, records: #node_list) -> Self {
+                let mut data = self.clone();
+                let mut records = records;
+
+                let (page, per_page, _) = self.limit();
+                let has_more = records.0.len() as i64 > per_page;
+                records.0.truncate(per_page as usize);
+
+                if data.before.is_some() {
+                    records.0.reverse();
+                }
+
+                // Walking backwards came from a later page, walking forwards from an earlier one
+                let (has_next, has_prev) = match data.before.is_some() {
+                    true => (true, has_more),
+                    false => (has_more, data.after.is_some() || page > 1),
+                };
+
+                let keys = self.sort_keys().unwrap_or_default();
+                if !keys.is_empty() {
+                    let cursor = |record: &#node| derives::Cursor::new(keys
+                        .iter()
+                        .map(|(col, ..)| Self::sort_value(record, col))
+                        .collect()
+                    ).encode();
+
+                    data.prev_cursor = records.0.first().filter(|_| has_prev).map(cursor);
+                    data.next_cursor = records.0.last().filter(|_| has_next).map(cursor);
+                }
+
+                data.records = Some(records);
+                data
+            }
+
+            pub fn where_clause(&self) -> errors::Result<#conditions> {
+                let mut conds = self.filters()?;
+
+                if let Some(search) = self.search_clause() {
+                    conds.and(search);
+                }
+
+                Ok(conds)
+            }
+
+            pub fn total_count_query(base_query: &str) -> String {
+                format!("SELECT COUNT(*) FROM ({}) AS total", base_query)
+            }
+
+            pub fn filtered_count_query(&self, base_query: &str) -> errors::Result<sqlx::QueryBuilder<'static, sqlx::Postgres>> {
+                let mut builder = sqlx::QueryBuilder::new(format!("SELECT COUNT(*) FROM ({}", base_query));
+
+                self.where_clause()?.apply(&mut builder);
+                builder.push(") AS filtered");
+
+                Ok(builder)
+            }
+
+            pub async fn fetch_page<'a, A>(&self, conn: A, base_query: &str) -> errors::Result<Self>
+                where A: sqlx::Acquire<'a, Database = sqlx::Postgres>
+            {
+                self.fetch_scoped_page(conn, base_query, #conditions::default()).await
+            }
+
+            // Scope conditions (tenant, owner, soft delete) apply to both counts and the records
+            pub async fn fetch_scoped_page<'a, A>(&self, conn: A, base_query: &str, scope: #conditions) -> errors::Result<Self>
+                where A: sqlx::Acquire<'a, Database = sqlx::Postgres>
+            {
+                let mut conn = conn.acquire().await.map_err(errors::query)?;
+                let mut data = self.request();
+
+                let mut builder = sqlx::QueryBuilder::new(format!("SELECT COUNT(*) FROM ({}", base_query));
+                scope.apply(&mut builder);
+                builder.push(") AS total");
+
+                let total_count = builder
+                    .build_query_scalar::<i64>()
+                    .fetch_one(&mut *conn)
+                    .await
+                    .map_err(errors::query)?;
+
+                let mut conds = scope;
+                conds.extend(data.where_clause()?);
+
+                let mut builder = sqlx::QueryBuilder::new(format!("SELECT COUNT(*) FROM ({}", base_query));
+                conds.apply(&mut builder);
+                builder.push(") AS filtered");
+
+                let filtered_count = builder
+                    .build_query_scalar::<i64>()
+                    .fetch_one(&mut *conn)
+                    .await
+                    .map_err(errors::query)?;
+
+                data.total_count = Some(total_count);
+                data.filtered_count = Some(filtered_count);
+
+                // Keyset condition only applies to the data query
+                conds.extend(data.keyset()?);
+
+                let order = data.orders()?;
+
+                let (page, per_page, offset) = data.limit();
+                data.page = Some(page);
+                data.per_page = Some(per_page);
+
+                let mut builder = sqlx::QueryBuilder::new(base_query);
+                conds.apply(&mut builder);
+                builder.push(format!(" {} LIMIT {} OFFSET {}", order, per_page + 1, offset));
+
+                let rows = builder
+                    .build()
+                    .fetch_all(&mut *conn)
+                    .await
+                    .map_err(errors::query)?;
+
+                let mut records = #node_list::default();
+                for row in rows {
+                    records.push(#module::parsers::parse(&row));
+                }
+
+                Ok(data.with_cursors(records).response())
+            }
+
+            pub fn response(&self) -> Self {
+                let mut data = self.clone();
+
+                // Navigation is only known for offset pages with a filtered count
+                let is_keyset = data.after.is_some() || data.before.is_some();
+                if let (false, Some(total_pages)) = (is_keyset, data.total_pages()) {
+                    let (page, per_page, _) = data.limit();
+
+                    data.page = Some(page);
+                    data.per_page = Some(per_page);
+                    data.total_pages = Some(total_pages);
+                    data.has_next = Some(page < total_pages);
+                    data.has_prev = Some(page > 1);
+                }
+
+                if is_keyset {
+                    data.has_next = Some(data.next_cursor.is_some());
+                    data.has_prev = Some(data.prev_cursor.is_some());
+                }
+
+                data.search = None;
+                data.filters = None;
+                data.orders = None;
+                data.after = None;
+                data.before = None;
+
+                data
+            }
+        }
+    }
+}
\ No newline at end of file
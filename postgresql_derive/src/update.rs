@@ -0,0 +1,86 @@
+use proc_macro2::TokenStream;
+use syn::{Ident, Type};
+
+use crate::extractors;
+
+pub fn stream(
+    node: &Ident,
+    table: &str,
+    key: (&Ident, &str, &Type),
+    fields: &[Ident],
+    columns: &[String],
+    types: &[Type]
+) -> TokenStream {
+    let module = extractors::module_name(node);
+    let (key_field, key, key_type) = key;
+
+    // Key column is only used under the WHERE clause
+    let mut set_fields = vec![];
+    let mut set_columns = vec![];
+    let mut set_types = vec![];
+
+    for (index, field) in fields.iter().enumerate() {
+        if field != key_field {
+            set_fields.push(field.clone());
+            set_columns.push(columns[index].clone());
+            set_types.push(types[index].clone());
+        }
+    }
+
+    let fields = &set_fields;
+    let columns = &set_columns;
+    let types = &set_types;
+
+    quote::quote! {
+        impl #node {
+            pub fn update_query(&self, key: #key_type) -> errors::Result<(String, sqlx::postgres::PgArguments)> {
+                use sqlx::Arguments;
+
+                let is_defined = false #(|| !self.#fields.is_undefined())*;
+                if !is_defined {
+                    return Err(errors::str_to("no fields to update"));
+                }
+
+                let mut sets: Vec<String> = vec![];
+                let mut arguments = sqlx::postgres::PgArguments::default();
+                let mut idx = 0;
+
+                #(
+                    match self.#fields.clone() {
+                        nulls::Null::Undefined => {},
+                        nulls::Null::Null => sets.push(format!("{} = NULL", #columns)),
+                        nulls::Null::Value(value) => {
+                            idx += 1;
+                            sets.push(format!("{} = ${}", #columns, idx));
+                            let _ = arguments.add::<#types>(value);
+                        }
+                    }
+                )*
+
+                let _ = arguments.add(key);
+
+                let query = format!(
+                    "UPDATE {} SET {} WHERE {} = ${} RETURNING {}",
+                    #table,
+                    sets.join(", "),
+                    #key,
+                    idx + 1,
+                    #module::alias::ALL
+                );
+
+                Ok((query, arguments))
+            }
+
+            pub async fn update<'e, E>(&self, executor: E, key: #key_type) -> errors::Result<Self>
+                where E: sqlx::PgExecutor<'e>
+            {
+                let (query, arguments) = self.update_query(key)?;
+                let row = sqlx::query_with(&query, arguments)
+                    .fetch_one(executor)
+                    .await;
+
+                #module::parsers::result(row)
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -5,9 +5,14 @@ pub use is_empty_derive::IsEmpty;
 pub use jsonb_derive::Jsonb;
 pub use postgresql_derive::PostgreSQL;
 
+pub use derives_runtime::{
+    ColumnMeta, Cursor, Filter, FilterNode, FilterOps, Order, OrderNulls, OrderOps,
+    PageQuery, ParseError, ParseErrorKind, UnknownVariant
+};
+
 pub trait Encryption {}
 pub trait Enums {}
 pub trait Form {}
 pub trait IsEmpty {}
 pub trait Jsonb {}
-pub trait PostgreSQL {}
+pub trait PostgreSQL {}
\ No newline at end of file